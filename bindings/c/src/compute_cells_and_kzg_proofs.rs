@@ -1,6 +1,6 @@
 use crate::pointer_utils::{create_array_ref, deref_const, write_to_2d_slice};
 use crate::{CResult, DASContext};
-use rust_eth_kzg::constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB};
+use rust_eth_kzg::constants::BYTES_PER_BLOB;
 
 pub(crate) fn _compute_cells_and_kzg_proofs(
     ctx: *const DASContext,
@@ -20,11 +20,10 @@ pub(crate) fn _compute_cells_and_kzg_proofs(
     let (cells, proofs) = ctx
         .compute_cells_and_kzg_proofs(blob)
         .map_err(|err| CResult::with_error(&format!("{:?}", err)))?;
-    let cells_unboxed = cells.map(|cell| cell.to_vec());
 
     // Write to output
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_cells, cells_unboxed);
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_proofs, proofs);
+    write_to_2d_slice(out_cells, cells);
+    write_to_2d_slice(out_proofs, proofs);
 
     Ok(())
 }
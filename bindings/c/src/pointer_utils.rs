@@ -51,11 +51,8 @@ pub(crate) fn write_to_slice<T: Copy>(ptr: *mut T, data: &[T]) {
 
 /// Write `data` to a 2D slice starting at `ptr`
 // TODO: the data parameter might be too complicated, investigate simplifying it
-pub(crate) fn write_to_2d_slice<T: Copy, const N: usize>(
-    ptr: *mut *mut T,
-    data: [impl AsRef<[T]>; N],
-) {
-    let out_cells = ptr_ptr_to_slice_slice_mut(ptr, N);
+pub(crate) fn write_to_2d_slice<T: Copy>(ptr: *mut *mut T, data: Vec<impl AsRef<[T]>>) {
+    let out_cells = ptr_ptr_to_slice_slice_mut(ptr, data.len());
 
     for (out_cell, result) in out_cells.iter_mut().zip(data) {
         write_to_slice(*out_cell, result.as_ref());
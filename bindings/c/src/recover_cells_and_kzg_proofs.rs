@@ -2,7 +2,7 @@ use crate::pointer_utils::{
     create_slice_view, deref_const, ptr_ptr_to_vec_slice_const, write_to_2d_slice,
 };
 use crate::{CResult, DASContext};
-use rust_eth_kzg::constants::{BYTES_PER_CELL, CELLS_PER_EXT_BLOB};
+use rust_eth_kzg::constants::BYTES_PER_CELL;
 
 pub(crate) fn _recover_cells_and_proofs(
     ctx: *const DASContext,
@@ -19,6 +19,7 @@ pub(crate) fn _recover_cells_and_proofs(
     //
     let ctx = deref_const(ctx).inner();
     let cells = ptr_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize);
+    let cells = cells.into_iter().map(|cell| cell.as_slice()).collect();
     let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
 
     // Computation
@@ -26,11 +27,10 @@ pub(crate) fn _recover_cells_and_proofs(
     let (recovered_cells, recovered_proofs) = ctx
         .recover_cells_and_proofs(cell_indices.to_vec(), cells)
         .map_err(|err| CResult::with_error(&format!("{:?}", err)))?;
-    let recovered_cells_unboxed = recovered_cells.map(|cell| cell.to_vec());
 
     // Write to output
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_cells, recovered_cells_unboxed);
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_proofs, recovered_proofs);
+    write_to_2d_slice(out_cells, recovered_cells);
+    write_to_2d_slice(out_proofs, recovered_proofs);
 
     Ok(())
 }
@@ -47,6 +47,7 @@ pub(crate) fn _verify_cell_kzg_proof_batch(
     );
     let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
     let cells = ptr_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize);
+    let cells = cells.into_iter().map(|cell| cell.as_slice()).collect();
     let proofs = ptr_ptr_to_vec_slice_const::<BYTES_PER_COMMITMENT>(proofs, proofs_length as usize);
     let verified = deref_mut(verified);
 
@@ -51,7 +51,6 @@ fn compute_cells_and_kzg_proofs<'local>(
     let blob = slice_to_array_ref(&blob, "blob")?;
 
     let (cells, proofs) = ctx.inner().compute_cells_and_kzg_proofs(blob)?;
-    let cells = cells.map(|cell| *cell);
     cells_and_proofs_to_jobject(env, &cells, &proofs).map_err(Error::from)
 }
 
@@ -116,10 +115,8 @@ fn verify_cell_kzg_proof_batch<'local>(
     let cells = jobject_array_to_2d_byte_array(env, cells)?;
     let proofs = jobject_array_to_2d_byte_array(env, proofs)?;
 
-    let cells: Vec<_> = cells
-        .iter()
-        .map(|cell| slice_to_array_ref(cell, "cell"))
-        .collect::<Result<_, _>>()?;
+    // The number of bytes in each cell is checked by the library
+    let cells: Vec<_> = cells.iter().map(Vec::as_slice).collect();
     let commitments: Vec<_> = commitment
         .iter()
         .map(|commitment| slice_to_array_ref(commitment, "commitment"))
@@ -165,14 +162,11 @@ fn recover_cells_and_kzg_proofs<'local>(
 ) -> Result<JObject<'local>, Error> {
     let cell_ids = jlongarray_to_vec_u64(env, cell_ids)?;
     let cells = jobject_array_to_2d_byte_array(env, cells)?;
-    let cells: Vec<_> = cells
-        .iter()
-        .map(|cell| slice_to_array_ref(cell, "cell"))
-        .collect::<Result<_, _>>()?;
+    // The number of bytes in each cell is checked by the library
+    let cells: Vec<_> = cells.iter().map(Vec::as_slice).collect();
 
     let (recovered_cells, recovered_proofs) =
         ctx.inner().recover_cells_and_proofs(cell_ids, cells)?;
-    let recovered_cells = recovered_cells.map(|cell| *cell);
     cells_and_proofs_to_jobject(env, &recovered_cells, &recovered_proofs).map_err(Error::from)
 }
 
@@ -124,15 +124,11 @@ impl DASContextJs {
     cells: Vec<Uint8Array>,
   ) -> Result<CellsAndProofs> {
     let cell_indices: Vec<_> = cell_indices.into_iter().map(bigint_to_u64).collect();
+    // The number of bytes in each cell is checked by the library
     let cells: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
 
     let ctx = &self.inner;
 
-    let cells: Vec<_> = cells
-      .iter()
-      .map(|cell| slice_to_array_ref(cell, "cell"))
-      .collect::<Result<_, _>>()?;
-
     let (cells, proofs) = ctx
       .recover_cells_and_proofs(cell_indices, cells)
       .map_err(|err| {
@@ -180,10 +176,8 @@ impl DASContextJs {
       .iter()
       .map(|commitment| slice_to_array_ref(commitment, "commitment"))
       .collect::<Result<_, _>>()?;
-    let cells: Vec<_> = cells
-      .iter()
-      .map(|cell| slice_to_array_ref(cell, "cell"))
-      .collect::<Result<_, _>>()?;
+    // The number of bytes in each cell is checked by the library
+    let cells: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
     let proofs: Vec<_> = proofs
       .iter()
       .map(|proof| slice_to_array_ref(proof, "proof"))
@@ -41,6 +41,84 @@ impl FixedBaseMSM {
         }
     }
 
+    /// The number of points that the precomputed table was created from.
+    ///
+    /// This is also the number of scalars that `msm` expects.
+    pub fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    /// The number of bytes needed to serialize a single point in the precomputed table.
+    const SERIALIZED_POINT_SIZE: usize = 2 * 48;
+
+    /// Serializes the precomputed table, so that it can be loaded without redoing the precomputation.
+    ///
+    /// The layout is the following, where all integers are big-endian:
+    ///  - wbits: 8 bytes
+    ///  - num_points: 8 bytes
+    ///  - table: the affine `x` and `y` coordinates of each point, 48 bytes each
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + self.table.len() * Self::SERIALIZED_POINT_SIZE);
+
+        bytes.extend((self.wbits as u64).to_be_bytes());
+        bytes.extend((self.num_points as u64).to_be_bytes());
+
+        let mut coordinate = [0u8; 48];
+        for point in &self.table {
+            unsafe { blst::blst_bendian_from_fp(coordinate.as_mut_ptr(), &point.x) };
+            bytes.extend(coordinate);
+            unsafe { blst::blst_bendian_from_fp(coordinate.as_mut_ptr(), &point.y) };
+            bytes.extend(coordinate);
+        }
+
+        bytes
+    }
+
+    /// Deserializes a precomputed table that was serialized using `to_bytes`.
+    ///
+    /// Returns `None` if the bytes do not have the expected layout.
+    ///
+    /// Note: The points in the table are not checked, so this should only be used
+    /// with bytes that come from a trusted source.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 16 {
+            return None;
+        }
+        let (header, table_bytes) = bytes.split_at(16);
+        let wbits = usize::try_from(u64::from_be_bytes(header[..8].try_into().ok()?)).ok()?;
+        let num_points = usize::try_from(u64::from_be_bytes(header[8..].try_into().ok()?)).ok()?;
+
+        // The table contains 2^(wbits-1) points for each generator.
+        if wbits == 0 || wbits >= usize::BITS as usize {
+            return None;
+        }
+        let table_size = num_points.checked_mul(1 << (wbits - 1))?;
+        if table_bytes.len() != table_size.checked_mul(Self::SERIALIZED_POINT_SIZE)? {
+            return None;
+        }
+
+        let table = table_bytes
+            .chunks_exact(Self::SERIALIZED_POINT_SIZE)
+            .map(|point_bytes| {
+                let mut point = blst::blst_p1_affine::default();
+                unsafe {
+                    blst::blst_fp_from_bendian(&mut point.x, point_bytes[..48].as_ptr());
+                    blst::blst_fp_from_bendian(&mut point.y, point_bytes[48..].as_ptr());
+                }
+                point
+            })
+            .collect();
+
+        let scratch_space_size = unsafe { blst::blst_p1s_mult_wbits_scratch_sizeof(num_points) };
+
+        Some(FixedBaseMSM {
+            table,
+            wbits,
+            num_points,
+            scratch_space_size,
+        })
+    }
+
     pub fn msm(&self, scalars: Vec<Scalar>) -> G1Projective {
         use ff::PrimeField;
         let mut ret = blst::blst_p1::default();
@@ -103,6 +181,28 @@ mod tests {
         assert_eq!(res, result);
     }
 
+    #[test]
+    fn fixed_base_msm_serialization_round_trip() {
+        let length = 64;
+        let generators: Vec<_> = (0..length)
+            .map(|_| G1Projective::random(&mut rand::thread_rng()).into())
+            .collect();
+        let scalars: Vec<_> = (0..length)
+            .map(|_| Scalar::random(&mut thread_rng()))
+            .collect();
+
+        let fbm = FixedBaseMSM::new(generators, 8);
+        let bytes = fbm.to_bytes();
+        let fbm_from_bytes = FixedBaseMSM::from_bytes(&bytes).unwrap();
+
+        assert_eq!(fbm.msm(scalars.clone()), fbm_from_bytes.msm(scalars));
+        assert_eq!(fbm_from_bytes.to_bytes(), bytes);
+
+        // Truncated tables should be rejected
+        assert!(FixedBaseMSM::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(FixedBaseMSM::from_bytes(&bytes[..10]).is_none());
+    }
+
     #[test]
     fn fixed_base_msm_non_zero() {
         // All elements in the table should be non-zero
@@ -51,6 +51,29 @@ pub fn reduce_bytes_to_scalar_bias(mut bytes: [u8; 32]) -> Scalar {
     Scalar::from_bytes_be(&bytes).expect("254 bit integer should have been reducible to a scalar")
 }
 
+/// Interprets the bytes as a big-endian integer and reduces it modulo the scalar field order.
+///
+/// Unlike `reduce_bytes_to_scalar_bias`, this does not truncate the input and will
+/// match the `hash_to_bls_field` method in the consensus specs.
+pub fn reduce_bytes_to_scalar(bytes: [u8; 32]) -> Scalar {
+    // Split the 256 bit integer into two 128 bit integers, each of which
+    // is smaller than the scalar field order.
+    //
+    // The integer is then computed as hi * 2^128 + lo
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes"));
+    }
+    let lo = Scalar::from_u64s_le(&[limbs[0], limbs[1], 0, 0])
+        .expect("128 bit integer is smaller than the scalar field order");
+    let hi = Scalar::from_u64s_le(&[limbs[2], limbs[3], 0, 0])
+        .expect("128 bit integer is smaller than the scalar field order");
+    let two_pow_128 =
+        Scalar::from_u64s_le(&[0, 0, 1, 0]).expect("2^128 is smaller than the scalar field order");
+
+    hi * two_pow_128 + lo
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +118,33 @@ mod tests {
         .unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_reduce_bytes_to_scalar() {
+        // Integers less than the modulus are not changed
+        let mut input_bytes = [0u8; 32];
+        input_bytes[0] = 0x12;
+        input_bytes[15] = 0x34;
+        input_bytes[31] = 0x56;
+        let expected = Scalar::from_bytes_be(&input_bytes).unwrap();
+        assert_eq!(reduce_bytes_to_scalar(input_bytes), expected);
+
+        // The modulus reduces to zero
+        let modulus: [u8; 32] = [
+            0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1,
+            0xd8, 0x05, 0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff,
+            0x00, 0x00, 0x00, 0x01,
+        ];
+        assert_eq!(reduce_bytes_to_scalar(modulus), Scalar::ZERO);
+
+        // The modulus plus one reduces to one
+        let mut modulus_plus_one = modulus;
+        modulus_plus_one[31] = 0x02;
+        assert_eq!(reduce_bytes_to_scalar(modulus_plus_one), Scalar::ONE);
+
+        // 2^256 - 1 is equal to (2^128)^2 - 1
+        let two_pow_128 = Scalar::from_u64s_le(&[0, 0, 1, 0]).unwrap();
+        let expected = two_pow_128.square() - Scalar::ONE;
+        assert_eq!(reduce_bytes_to_scalar([0xFF; 32]), expected);
+    }
 }
@@ -44,5 +44,40 @@ pub fn bench_erasure_code_decoding_4096_8192(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, bench_erasure_code_decoding_4096_8192);
+pub fn bench_erasure_code_decoding_with_errors_4096_8192(c: &mut Criterion) {
+    use rand::prelude::SliceRandom;
+
+    const POLYNOMIAL_LEN: usize = 4096;
+
+    let block_size = 1; // Note: This is not used when decoding with errors
+    let expansion_factor = 2;
+    let rs = ReedSolomon::new(POLYNOMIAL_LEN, expansion_factor, block_size);
+    let extended_poly_len = rs.codeword_length();
+
+    let poly_coeff: Vec<_> = (0..POLYNOMIAL_LEN)
+        .map(|i| -Scalar::from(i as u64 + 1))
+        .collect();
+    let mut codeword = rs.encode(poly_coeff).unwrap();
+
+    let num_errors = rs.acceptable_num_errors();
+    let mut positions: Vec<_> = (0..extended_poly_len).collect();
+    positions.shuffle(&mut rand::thread_rng());
+    for position in positions.into_iter().take(num_errors) {
+        codeword[position] += Scalar::from(position as u64 + 1);
+    }
+
+    c.bench_function(
+        &format!(
+            "computing decoding with errors: EXT_SIZE {}, NUM_ERRORS {}",
+            extended_poly_len, num_errors
+        ),
+        |b| b.iter(|| rs.decode_with_errors(black_box(codeword.clone()))),
+    );
+}
+
+criterion_group!(
+    benches,
+    bench_erasure_code_decoding_4096_8192,
+    bench_erasure_code_decoding_with_errors_4096_8192
+);
 criterion_main!(benches);
@@ -16,4 +16,30 @@ pub enum RSError {
         block_index: usize,
         block_size: usize,
     },
+    TooManyErasures {
+        num_erasures: usize,
+        max_num_erasures_accepted: usize,
+    },
+    InvalidErasureIndex {
+        index: usize,
+        codeword_length: usize,
+    },
+    DuplicateErasureIndex {
+        index: usize,
+    },
+    CodewordHasInvalidLength {
+        codeword_length: usize,
+        expected_codeword_length: usize,
+    },
+    DataHasInvalidLength {
+        data_length: usize,
+        expected_data_length: usize,
+    },
+    TooManyErrors {
+        max_num_errors_accepted: usize,
+    },
+    VanishingPolyHasInvalidLength {
+        vanishing_poly_length: usize,
+        expected_vanishing_poly_length: usize,
+    },
 }
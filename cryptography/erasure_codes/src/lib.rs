@@ -1,3 +1,3 @@
 pub mod errors;
 mod reed_solomon;
-pub use reed_solomon::{BlockErasureIndices, ReedSolomon};
+pub use reed_solomon::{BlockErasureIndices, ErasureVanishingPoly, ReedSolomon};
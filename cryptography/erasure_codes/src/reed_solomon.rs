@@ -1,7 +1,15 @@
+use std::collections::HashSet;
+
 use bls12_381::{batch_inversion::batch_inverse, ff::Field, Scalar};
 
 use crate::errors::RSError;
-use polynomial::{domain::Domain, monomial::vanishing_poly};
+use polynomial::{
+    bit_reverse::{log2, reverse_bit_order, reverse_bits},
+    domain::Domain,
+    monomial::{
+        poly_div_rem, poly_mul, poly_sub, vanishing_poly, vanishing_poly_subproduct_tree, PolyCoeff,
+    },
+};
 
 /// ErasurePattern is an abstraction created to capture the idea
 /// that erasures do not appear in completely random locations.
@@ -22,8 +30,7 @@ pub(crate) enum ErasurePattern {
     BlockSynchronizedErasures(BlockErasureIndices),
     /// There is no pattern to the missing erasures.
     ///
-    /// This is used for tests.
-    #[cfg(test)]
+    /// Each index denotes the position of an erasure in the codeword.
     Random { indices: Vec<usize> },
 }
 
@@ -34,6 +41,19 @@ type BlockErasureIndex = usize;
 #[derive(Debug, Clone, Default)]
 pub struct BlockErasureIndices(pub Vec<BlockErasureIndex>);
 
+/// The polynomial that vanishes on the erasures of a codeword, evaluated over the
+/// evaluation domain and over its coset.
+///
+/// Computing this only depends on the positions of the erasures, so it can be shared
+/// when recovering many codewords that have erasures in the same positions.
+#[derive(Debug, Clone)]
+pub struct ErasureVanishingPoly {
+    /// Z(X) evaluated over the evaluation domain.
+    z_x_eval: Vec<Scalar>,
+    /// The inverse of Z(X) evaluated over the coset of the evaluation domain.
+    inv_coset_z_x_eval: Vec<Scalar>,
+}
+
 #[derive(Debug)]
 pub struct ReedSolomon {
     /// Denotes the factor by which the message/poly_len will be expanded.
@@ -41,6 +61,10 @@ pub struct ReedSolomon {
     expansion_factor: usize,
     /// The length of the polynomial that we will be encoding to a codeword.
     poly_len: usize,
+    /// The domain that we will use to interpolate the data, when using systematic encoding.
+    ///
+    /// Note: This domain will have size = poly_len.
+    poly_domain: Domain,
     /// The domain that we will use to convert the polynomial in coefficient form (msg)
     /// to a codeword.
     ///
@@ -77,6 +101,7 @@ impl ReedSolomon {
 
         Self {
             poly_len,
+            poly_domain: Domain::new(poly_len),
             evaluation_domain,
             expansion_factor,
             block_size,
@@ -89,7 +114,7 @@ impl ReedSolomon {
     /// tolerate before are not able to recover the message.
     ///
     /// Note: we need to have at least `poly_len` evaluations
-    fn acceptable_num_random_erasures(&self) -> usize {
+    pub fn acceptable_num_random_erasures(&self) -> usize {
         let total_codeword_len = self.poly_len * self.expansion_factor;
         let min_num_evaluations_needed = self.poly_len;
         total_codeword_len - min_num_evaluations_needed
@@ -101,6 +126,12 @@ impl ReedSolomon {
         self.acceptable_num_random_erasures() / self.num_blocks
     }
 
+    /// Returns the maximum number of errors, at unknown positions, that can be corrected
+    /// by `decode_with_errors`.
+    pub fn acceptable_num_errors(&self) -> usize {
+        self.acceptable_num_random_erasures() / 2
+    }
+
     /// The number of scalars in the reed solomon encoded polynomial
     pub fn codeword_length(&self) -> usize {
         self.poly_len * self.expansion_factor
@@ -118,6 +149,93 @@ impl ReedSolomon {
         Ok(self.evaluation_domain.fft_scalars(poly_coefficient_form))
     }
 
+    /// Encodes the data, so that the first `poly_len` values of the codeword are the data itself.
+    ///
+    /// The data is treated as the evaluations of a polynomial in bit-reversed order, and the
+    /// codeword is returned in bit-reversed order. This is the same ordering that FK20 uses for
+    /// `Input::Data`, so the codeword can be split into the cells of an extended blob.
+    pub fn encode_systematic(&self, mut data: Vec<Scalar>) -> Result<Vec<Scalar>, RSError> {
+        if data.len() != self.poly_len {
+            return Err(RSError::DataHasInvalidLength {
+                data_length: data.len(),
+                expected_data_length: self.poly_len,
+            });
+        }
+
+        reverse_bit_order(&mut data);
+        let poly_coeff = self.poly_domain.ifft_scalars(data);
+
+        let mut codeword = self.encode(poly_coeff)?;
+        reverse_bit_order(&mut codeword);
+        Ok(codeword)
+    }
+
+    /// Given a codeword that was returned by `encode_systematic` and the positions of its
+    /// erasures, this method will return the data that the codeword was created from.
+    ///
+    /// The positions are indices into the codeword, which is in bit-reversed order. The erasures
+    /// can be at any position in the codeword, as with `recover_polynomial_coefficient_random_erasure`.
+    pub fn decode_systematic(
+        &self,
+        partial_codeword: Vec<Scalar>,
+        erasures: Vec<usize>,
+    ) -> Result<Vec<Scalar>, RSError> {
+        if partial_codeword.len() != self.codeword_length() {
+            return Err(RSError::CodewordHasInvalidLength {
+                codeword_length: partial_codeword.len(),
+                expected_codeword_length: self.codeword_length(),
+            });
+        }
+        let vanishing_poly = self.systematic_erasure_vanishing_poly(erasures)?;
+
+        self.decode_systematic_with_vanishing_poly(partial_codeword, &vanishing_poly)
+    }
+
+    /// Computes the vanishing polynomial for erasures at the given positions of codewords
+    /// returned by `encode_systematic`, so that it can be shared when decoding many codewords
+    /// that are missing the same positions.
+    ///
+    /// See `decode_systematic_with_vanishing_poly`.
+    pub fn systematic_erasure_vanishing_poly(
+        &self,
+        erasures: Vec<usize>,
+    ) -> Result<ErasureVanishingPoly, RSError> {
+        // Check the erasures before they are permuted, so that errors refer to the positions given.
+        self.check_random_erasures(&erasures)?;
+
+        // Put the erasures in the order of the evaluation domain
+        let num_bits = log2(self.codeword_length() as u32);
+        let erasures = erasures
+            .into_iter()
+            .map(|index| reverse_bits(index, num_bits))
+            .collect();
+
+        let z_x = self.construct_vanishing_poly_from_erasure_pattern(ErasurePattern::Random {
+            indices: erasures,
+        })?;
+        Ok(self.evaluate_vanishing_poly(z_x))
+    }
+
+    /// Given a codeword that was returned by `encode_systematic` and the vanishing polynomial
+    /// for its erasures, this method will return the data that the codeword was created from.
+    ///
+    /// This is the same as `decode_systematic`, without recomputing the vanishing polynomial.
+    pub fn decode_systematic_with_vanishing_poly(
+        &self,
+        mut partial_codeword: Vec<Scalar>,
+        vanishing_poly: &ErasureVanishingPoly,
+    ) -> Result<Vec<Scalar>, RSError> {
+        // Put the codeword in the order of the evaluation domain
+        reverse_bit_order(&mut partial_codeword);
+
+        let poly_coeff = self
+            .recover_polynomial_coefficient_with_vanishing_poly(partial_codeword, vanishing_poly)?;
+
+        let mut data = self.poly_domain.fft_scalars(poly_coeff);
+        reverse_bit_order(&mut data);
+        Ok(data)
+    }
+
     /// Given a codeword and a list of its erasures,
     /// This method will return the polynomial in coefficient form
     /// that is able to generate the codeword with the erasures recovered.
@@ -132,8 +250,53 @@ impl ReedSolomon {
         )
     }
 
-    #[cfg(test)]
-    fn recover_polynomial_coefficient_random_erasure(
+    /// Computes the vanishing polynomial for the given block erasures, so that it can be
+    /// shared when recovering many codewords that are missing the same blocks.
+    ///
+    /// See `recover_polynomial_coefficient_with_vanishing_poly`.
+    pub fn erasure_vanishing_poly(
+        &self,
+        erasures: BlockErasureIndices,
+    ) -> Result<ErasureVanishingPoly, RSError> {
+        let z_x = self.construct_vanishing_poly_from_erasure_pattern(
+            ErasurePattern::BlockSynchronizedErasures(erasures),
+        )?;
+        Ok(self.evaluate_vanishing_poly(z_x))
+    }
+
+    /// Given a codeword and the vanishing polynomial for its erasures, this method will
+    /// return the polynomial in coefficient form that is able to generate the codeword
+    /// with the erasures recovered.
+    ///
+    /// This is the same as `recover_polynomial_coefficient`, without recomputing the
+    /// vanishing polynomial.
+    ///
+    /// An error is returned if the vanishing polynomial was computed for a code with a
+    /// different codeword length.
+    pub fn recover_polynomial_coefficient_with_vanishing_poly(
+        &self,
+        codeword_with_erasures: Vec<Scalar>,
+        vanishing_poly: &ErasureVanishingPoly,
+    ) -> Result<Vec<Scalar>, RSError> {
+        if vanishing_poly.z_x_eval.len() != self.codeword_length() {
+            return Err(RSError::VanishingPolyHasInvalidLength {
+                vanishing_poly_length: vanishing_poly.z_x_eval.len(),
+                expected_vanishing_poly_length: self.codeword_length(),
+            });
+        }
+        self.recover_polynomial_coefficient_from_vanishing_poly(
+            codeword_with_erasures,
+            vanishing_poly,
+        )
+    }
+
+    /// Given a codeword and the positions of its erasures, this method will return the
+    /// polynomial in coefficient form that is able to generate the codeword with the
+    /// erasures recovered.
+    ///
+    /// Unlike `recover_polynomial_coefficient`, the erasures can be at any position in the codeword.
+    /// This is slower, so block erasures should be preferred when the erasures follow that pattern.
+    pub fn recover_polynomial_coefficient_random_erasure(
         &self,
         codeword_with_erasures: Vec<Scalar>,
         random_erasure: Vec<usize>,
@@ -146,6 +309,100 @@ impl ReedSolomon {
         )
     }
 
+    /// Given a codeword where some of the values may be incorrect, this method will return
+    /// the polynomial in coefficient form that generates the corrected codeword, along with
+    /// the positions of the values that were incorrect, in ascending order.
+    ///
+    /// Unlike erasures, the positions of the errors do not need to be known. At most
+    /// `acceptable_num_errors` errors can be corrected. If there are more errors than this,
+    /// an error is returned, unless the codeword happens to be close to a different codeword.
+    ///
+    // This uses Gao's algorithm: https://www.math.clemson.edu/~sgao/papers/RS.pdf
+    pub fn decode_with_errors(
+        &self,
+        codeword: Vec<Scalar>,
+    ) -> Result<(Vec<Scalar>, Vec<usize>), RSError> {
+        let n = self.codeword_length();
+        if codeword.len() != n {
+            return Err(RSError::CodewordHasInvalidLength {
+                codeword_length: codeword.len(),
+                expected_codeword_length: n,
+            });
+        }
+        let too_many_errors = RSError::TooManyErrors {
+            max_num_errors_accepted: self.acceptable_num_errors(),
+        };
+
+        // Interpolate the codeword, so that g_1(X) agrees with the codeword on the evaluation domain.
+        // g_0(X) = X^n - 1 is the polynomial that vanishes on the evaluation domain.
+        //
+        // Gao's algorithm runs the extended euclidean algorithm on g_0 and g_1, until the remainder
+        // has degree less than (n + k) / 2. Up until then, the quotients only depend on the
+        // coefficients of degree at least k, so we drop the lower coefficients. Since the message
+        // has k coefficients, the higher coefficients of g_1 only depend on the errors.
+        let k = self.poly_len;
+        let mut g_1 = self.evaluation_domain.ifft_scalars(codeword.clone());
+        let high_g_1 = g_1.split_off(k);
+        let mut high_g_0 = vec![Scalar::ZERO; n - k + 1];
+        high_g_0[n - k] = Scalar::ONE;
+
+        // We only need to track the Bezout coefficient v(X) of g_1, which is the error
+        // locator polynomial. ie it vanishes on the positions of the errors.
+        let error_locator = partial_extended_gcd(high_g_0, high_g_1);
+
+        // Find the roots of the error locator, by evaluating it over the evaluation domain.
+        //
+        // If there are too many errors, the error locator will usually not have as
+        // many roots in the evaluation domain as its degree.
+        let error_locator_eval = self.evaluation_domain.fft_scalars(error_locator.clone());
+        let error_positions: Vec<_> = error_locator_eval
+            .iter()
+            .enumerate()
+            .filter(|(_, eval)| **eval == Scalar::ZERO)
+            .map(|(position, _)| position)
+            .collect();
+        if error_positions.len() + 1 != error_locator.len() {
+            return Err(too_many_errors);
+        }
+
+        // Treat the errors as erasures, using the error locator as the vanishing polynomial.
+        let vanishing_poly = self.evaluate_vanishing_poly(error_locator);
+        let poly_coeff = self
+            .recover_polynomial_coefficient_from_vanishing_poly(codeword, &vanishing_poly)
+            .map_err(|_| too_many_errors)?;
+
+        Ok((poly_coeff, error_positions))
+    }
+
+    /// Checks that each erasure index is valid and appears once, and that there are not
+    /// too many erasures to recover from.
+    fn check_random_erasures(&self, indices: &[usize]) -> Result<(), RSError> {
+        let mut seen_indices = HashSet::with_capacity(indices.len());
+        for index in indices {
+            if *index >= self.codeword_length() {
+                return Err(RSError::InvalidErasureIndex {
+                    index: *index,
+                    codeword_length: self.codeword_length(),
+                });
+            }
+            if !seen_indices.insert(*index) {
+                return Err(RSError::DuplicateErasureIndex { index: *index });
+            }
+        }
+
+        // This method is only used for recovery.
+        // Check that we do not have too many erasures, such that we cannot
+        // recover.
+        if indices.len() > self.acceptable_num_random_erasures() {
+            return Err(RSError::TooManyErasures {
+                num_erasures: indices.len(),
+                max_num_erasures_accepted: self.acceptable_num_random_erasures(),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Constructs a polynomial that vanishes on all of the block indices in each block.
     ///
     /// This method makes the following assumptions:
@@ -244,14 +501,8 @@ impl ReedSolomon {
                 }
                 Ok(self.construct_vanishing_poly_from_block_erasures(indices))
             }
-            #[cfg(test)]
             ErasurePattern::Random { indices } => {
-                assert!(
-                    indices.len() <= self.acceptable_num_random_erasures(),
-                    "num random erasures = {} but tolerable erasures = {}",
-                    indices.len(),
-                    self.acceptable_num_random_erasures()
-                );
+                self.check_random_erasures(&indices)?;
                 // Computes the polynomial in coefficient form, that vanishes
                 // on all of the \omega^i roots, where `i` is taken from the indices vector
                 // and \omega is a primitive root of unity used to generate the evaluation domain.
@@ -259,7 +510,7 @@ impl ReedSolomon {
                     .into_iter()
                     .map(|index| self.evaluation_domain.roots[index])
                     .collect();
-                Ok(vanishing_poly(&roots))
+                Ok(vanishing_poly_subproduct_tree(&roots))
             }
         }
     }
@@ -273,13 +524,43 @@ impl ReedSolomon {
         // Compute Z(X) which is the polynomial that vanishes on all
         // of the missing points
         let z_x = self.construct_vanishing_poly_from_erasure_pattern(erasure)?;
+        let vanishing_poly = self.evaluate_vanishing_poly(z_x);
+
+        self.recover_polynomial_coefficient_from_vanishing_poly(data_eval, &vanishing_poly)
+    }
 
+    /// Evaluates the vanishing polynomial Z(X) over the evaluation domain and its coset.
+    fn evaluate_vanishing_poly(&self, z_x: Vec<Scalar>) -> ErasureVanishingPoly {
         // Compute Z(X)_eval which is the vanishing polynomial evaluated
         // at the missing points
         let z_x_eval = self.evaluation_domain.fft_scalars(z_x.clone());
 
+        let mut inv_coset_z_x_eval = self.evaluation_domain.coset_fft_scalars(z_x);
+        // We know that none of the values will be zero since we are evaluating z_x
+        // over a coset, that we know it has no roots in.
+        batch_inverse(&mut inv_coset_z_x_eval);
+
+        ErasureVanishingPoly {
+            z_x_eval,
+            inv_coset_z_x_eval,
+        }
+    }
+
+    fn recover_polynomial_coefficient_from_vanishing_poly(
+        &self,
+        data_eval: Vec<Scalar>,
+        vanishing_poly: &ErasureVanishingPoly,
+    ) -> Result<Vec<Scalar>, RSError> {
+        if data_eval.len() != self.codeword_length() {
+            return Err(RSError::CodewordHasInvalidLength {
+                codeword_length: data_eval.len(),
+                expected_codeword_length: self.codeword_length(),
+            });
+        }
+
         // Compute (D * Z)(X) or (E * Z)(X) (same polynomials)
-        let ez_eval: Vec<_> = z_x_eval
+        let ez_eval: Vec<_> = vanishing_poly
+            .z_x_eval
             .iter()
             .zip(data_eval)
             .map(|(zx, d)| zx * d)
@@ -288,13 +569,9 @@ impl ReedSolomon {
         let dz_poly = self.evaluation_domain.ifft_scalars(ez_eval);
 
         let coset_dz_eval = self.evaluation_domain.coset_fft_scalars(dz_poly);
-        let mut inv_coset_z_x_eval = self.evaluation_domain.coset_fft_scalars(z_x);
-        // We know that none of the values will be zero since we are evaluating z_x
-        // over a coset, that we know it has no roots in.
-        batch_inverse(&mut inv_coset_z_x_eval);
         let coset_quotient_eval: Vec<_> = coset_dz_eval
             .iter()
-            .zip(inv_coset_z_x_eval)
+            .zip(&vanishing_poly.inv_coset_z_x_eval)
             .map(|(d, zx_inv)| d * zx_inv)
             .collect();
 
@@ -320,11 +597,152 @@ impl ReedSolomon {
     }
 }
 
+/// Runs the extended euclidean algorithm on `a(X)` and `b(X)`, stopping at the first
+/// remainder `r(X)` whose degree is less than half the degree of `a(X)`, rounded up.
+///
+/// Returns the polynomial `v(X)`, such that `r(X) = u(X) * a(X) + v(X) * b(X)` for some `u(X)`.
+///
+/// `a(X)` must have a larger degree than `b(X)`.
+fn partial_extended_gcd(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
+    let (a, b) = (poly_trim(a), poly_trim(b));
+    let [_, [_, v]] = half_gcd(&a, &b);
+    v
+}
+
+/// Below this many coefficients, running the euclidean algorithm step by step is faster
+/// than recursing.
+const HALF_GCD_THRESHOLD: usize = 256;
+
+/// A 2x2 matrix of polynomials, which maps two consecutive remainders of the euclidean
+/// algorithm to two later remainders.
+type PolyMatrix = [[PolyCoeff; 2]; 2];
+
+/// Computes the matrix that maps `(a(X), b(X))` to the first two consecutive remainders
+/// `(r_i(X), r_(i+1)(X))` of the euclidean algorithm, such that the degree of `r_(i+1)(X)`
+/// is less than half the degree of `a(X)`, rounded up.
+///
+/// `a(X)` and `b(X)` must not have trailing zeros, and `a(X)` must have a larger degree
+/// than `b(X)`.
+///
+// This is the half-GCD algorithm, which takes O(n log^2 n) operations instead of the
+// O(n^2) operations needed by the euclidean algorithm.
+// See: Thull and Yap, "A unified approach to HGCD algorithms for polynomials and integers"
+//
+// The quotients of the first steps only depend on the higher coefficients of `a(X)` and `b(X)`.
+// So we run the half-GCD recursively on the higher half of the coefficients, to get halfway
+// there, do one step of the euclidean algorithm, and then recurse once more.
+fn half_gcd(a: &[Scalar], b: &[Scalar]) -> PolyMatrix {
+    // We stop once the remainder has at most `m` coefficients
+    let m = a.len() / 2;
+    if b.len() <= m {
+        return poly_matrix_identity();
+    }
+    if a.len() <= HALF_GCD_THRESHOLD {
+        return euclidean_algorithm_matrix(a.to_vec(), b.to_vec(), m);
+    }
+
+    let matrix = half_gcd(&a[m..], &b[m..]);
+    let [[c], [d]] = poly_matrix_mul(&matrix, [[a], [b]]);
+    if d.len() <= m {
+        return matrix;
+    }
+
+    let (quotient, e) = poly_div_rem(&c, &d);
+    let matrix = euclidean_step_matrix(quotient, matrix);
+    if e.len() <= m {
+        return matrix;
+    }
+
+    // `d(X)` has degree less than 2m, so this drops a part of the lower coefficients
+    // such that the recursive call stops at a remainder with at most `m` coefficients.
+    let shift = 2 * m - (d.len() - 1);
+    let remaining_matrix = half_gcd(&d[shift..], &e[shift..]);
+    let [[m_00, m_01], [m_10, m_11]] = &matrix;
+    poly_matrix_mul(&remaining_matrix, [[m_00, m_01], [m_10, m_11]])
+}
+
+/// Runs the euclidean algorithm step by step on `a(X)` and `b(X)`, stopping at the first
+/// remainder with at most `max_len` coefficients, and returns the matrix that maps `(a(X), b(X))`
+/// to the last two remainders.
+fn euclidean_algorithm_matrix(mut a: PolyCoeff, mut b: PolyCoeff, max_len: usize) -> PolyMatrix {
+    let mut matrix = poly_matrix_identity();
+    while b.len() > max_len {
+        let (quotient, remainder) = poly_div_rem(&a, &b);
+        matrix = euclidean_step_matrix(quotient, matrix);
+        a = std::mem::replace(&mut b, remainder);
+    }
+    matrix
+}
+
+/// Given the matrix that maps `(a(X), b(X))` to `(r_i(X), r_(i+1)(X))`, and the quotient of
+/// `r_i(X)` divided by `r_(i+1)(X)`, this method returns the matrix that maps `(a(X), b(X))`
+/// to `(r_(i+1)(X), r_(i+2)(X))`.
+fn euclidean_step_matrix(quotient: PolyCoeff, matrix: PolyMatrix) -> PolyMatrix {
+    let [[m_00, m_01], [m_10, m_11]] = matrix;
+    let next_m_10 = poly_trim(poly_sub(m_00, poly_mul(quotient.clone(), m_10.clone())));
+    let next_m_11 = poly_trim(poly_sub(m_01, poly_mul(quotient, m_11.clone())));
+    [[m_10, m_11], [next_m_10, next_m_11]]
+}
+
+/// Returns the matrix that maps `(a(X), b(X))` to itself.
+fn poly_matrix_identity() -> PolyMatrix {
+    [
+        [vec![Scalar::ONE], Vec::new()],
+        [Vec::new(), vec![Scalar::ONE]],
+    ]
+}
+
+/// Computes the product of a 2x2 matrix and a matrix with two rows.
+///
+/// The entries are multiplied in evaluation form, so that we only need one FFT per entry,
+/// and one inverse FFT per entry of the result.
+fn poly_matrix_mul<const N: usize>(
+    lhs: &PolyMatrix,
+    rhs: [[&[Scalar]; N]; 2],
+) -> [[PolyCoeff; N]; 2] {
+    let lhs_max_len = lhs.iter().flatten().map(Vec::len).max().unwrap_or(0);
+    let rhs_max_len = rhs
+        .iter()
+        .flatten()
+        .map(|entry| entry.len())
+        .max()
+        .unwrap_or(0);
+    let domain = Domain::new(lhs_max_len + rhs_max_len - 1);
+
+    let lhs_eval = lhs
+        .clone()
+        .map(|row| row.map(|entry| domain.fft_scalars(entry)));
+    let rhs_eval = rhs.map(|row| row.map(|entry| domain.fft_scalars(entry.to_vec())));
+
+    std::array::from_fn(|i| {
+        std::array::from_fn(|j| {
+            let product_eval = (0..domain.roots.len())
+                .map(|k| {
+                    lhs_eval[i][0][k] * rhs_eval[0][j][k] + lhs_eval[i][1][k] * rhs_eval[1][j][k]
+                })
+                .collect();
+            poly_trim(domain.ifft_scalars(product_eval))
+        })
+    })
+}
+
+/// Removes the trailing zeros, so that the length of the polynomial is its degree plus one.
+fn poly_trim(mut poly: PolyCoeff) -> PolyCoeff {
+    while poly.last() == Some(&Scalar::ZERO) {
+        poly.pop();
+    }
+    poly
+}
+
 #[cfg(test)]
 mod tests {
     use bls12_381::{ff::Field, Scalar};
 
-    use crate::{reed_solomon::ErasurePattern, BlockErasureIndices, ReedSolomon};
+    use crate::{
+        errors::RSError,
+        reed_solomon::{euclidean_algorithm_matrix, half_gcd, ErasurePattern},
+        BlockErasureIndices, ReedSolomon,
+    };
 
     #[test]
     #[should_panic]
@@ -507,4 +925,255 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn shared_vanishing_poly_recovers_many_codewords() {
+        const POLY_LEN: usize = 128;
+        const EXPANSION_FACTOR: usize = 2;
+        const BLOCK_SIZE: usize = 4;
+
+        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let missing_block_indices = vec![1, 3];
+        let vanishing_poly = rs
+            .erasure_vanishing_poly(BlockErasureIndices(missing_block_indices.clone()))
+            .unwrap();
+
+        for offset in 0..3u64 {
+            let poly_coeff: Vec<_> = (0..POLY_LEN)
+                .map(|i| Scalar::from(i as u64 + offset))
+                .collect();
+
+            let mut codeword_with_erasures = rs.encode(poly_coeff.clone()).unwrap();
+            for block in codeword_with_erasures.chunks_mut(BLOCK_SIZE) {
+                for index in &missing_block_indices {
+                    block[*index] = Scalar::ZERO;
+                }
+            }
+
+            let recovered_poly_coeff = rs
+                .recover_polynomial_coefficient_with_vanishing_poly(
+                    codeword_with_erasures.clone(),
+                    &vanishing_poly,
+                )
+                .unwrap();
+            assert_eq!(recovered_poly_coeff, poly_coeff);
+            assert_eq!(
+                rs.recover_polynomial_coefficient(
+                    codeword_with_erasures,
+                    BlockErasureIndices(missing_block_indices.clone())
+                )
+                .unwrap(),
+                poly_coeff
+            );
+        }
+
+        // Too many erasures are rejected when computing the vanishing polynomial
+        assert!(rs
+            .erasure_vanishing_poly(BlockErasureIndices(vec![0, 1, 2]))
+            .is_err());
+
+        // A vanishing polynomial computed for a different code is rejected
+        let other_rs = ReedSolomon::new(POLY_LEN * 2, EXPANSION_FACTOR, BLOCK_SIZE);
+        assert!(matches!(
+            other_rs.recover_polynomial_coefficient_with_vanishing_poly(
+                vec![Scalar::ZERO; other_rs.codeword_length()],
+                &vanishing_poly
+            ),
+            Err(RSError::VanishingPolyHasInvalidLength {
+                vanishing_poly_length,
+                expected_vanishing_poly_length,
+            }) if vanishing_poly_length == rs.codeword_length()
+                && expected_vanishing_poly_length == other_rs.codeword_length()
+        ));
+    }
+
+    #[test]
+    fn recovery_with_scattered_random_erasures() {
+        const POLY_LEN: usize = 256;
+        const EXPANSION_FACTOR: usize = 2;
+        const BLOCK_SIZE: usize = 1; // Note: This is not used for random erasures
+
+        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let poly_coeff: Vec<_> = (0..POLY_LEN).map(|i| -Scalar::from(i as u64)).collect();
+        let original_codeword = rs.encode(poly_coeff.clone()).unwrap();
+
+        // Since 37 is coprime to the codeword length, these indices are all distinct
+        let missing_indices: Vec<_> = (0..rs.acceptable_num_random_erasures())
+            .map(|i| (i * 37) % rs.codeword_length())
+            .collect();
+
+        // The values at the erasures are ignored, so they do not need to be zero
+        let mut codeword_with_erasures = original_codeword.clone();
+        for index in &missing_indices {
+            codeword_with_erasures[*index] = Scalar::from(12345u64);
+        }
+
+        let recovered_poly_coeff = rs
+            .recover_polynomial_coefficient_random_erasure(codeword_with_erasures, missing_indices)
+            .unwrap();
+        assert_eq!(recovered_poly_coeff, poly_coeff);
+    }
+
+    #[test]
+    fn decoding_corrects_errors_at_unknown_positions() {
+        const POLY_LEN: usize = 256;
+        const EXPANSION_FACTOR: usize = 2;
+        const BLOCK_SIZE: usize = 1; // Note: This is not used when decoding with errors
+
+        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let poly_coeff: Vec<_> = (0..POLY_LEN).map(|i| -Scalar::from(i as u64)).collect();
+        let original_codeword = rs.encode(poly_coeff.clone()).unwrap();
+
+        // A codeword without errors is decoded as is
+        let (decoded_poly_coeff, error_positions) =
+            rs.decode_with_errors(original_codeword.clone()).unwrap();
+        assert_eq!(decoded_poly_coeff, poly_coeff);
+        assert!(error_positions.is_empty());
+
+        // Since 37 is coprime to the codeword length, these positions are all distinct
+        let corrupt_codeword = |num_errors: usize| {
+            let mut positions: Vec<_> = (0..num_errors)
+                .map(|i| (i * 37) % rs.codeword_length())
+                .collect();
+            positions.sort();
+
+            let mut codeword = original_codeword.clone();
+            for position in &positions {
+                codeword[*position] += Scalar::from(*position as u64 + 1);
+            }
+            (codeword, positions)
+        };
+
+        for num_errors in [1, 17, rs.acceptable_num_errors()] {
+            let (codeword, positions) = corrupt_codeword(num_errors);
+            let (decoded_poly_coeff, error_positions) = rs.decode_with_errors(codeword).unwrap();
+            assert_eq!(decoded_poly_coeff, poly_coeff);
+            assert_eq!(error_positions, positions);
+        }
+
+        let (codeword, _) = corrupt_codeword(rs.acceptable_num_errors() + 1);
+        assert!(matches!(
+            rs.decode_with_errors(codeword),
+            Err(RSError::TooManyErrors {
+                max_num_errors_accepted: 128
+            })
+        ));
+        assert!(matches!(
+            rs.decode_with_errors(original_codeword[1..].to_vec()),
+            Err(RSError::CodewordHasInvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn half_gcd_matches_euclidean_algorithm() {
+        let mut rng = rand::thread_rng();
+
+        for (a_len, b_len) in [(2, 1), (129, 128), (1000, 999), (1025, 1024), (2049, 1500)] {
+            let a: Vec<_> = (0..a_len).map(|_| Scalar::random(&mut rng)).collect();
+            let b: Vec<_> = (0..b_len).map(|_| Scalar::random(&mut rng)).collect();
+
+            let expected = euclidean_algorithm_matrix(a.clone(), b.clone(), a_len / 2);
+            assert_eq!(half_gcd(&a, &b), expected);
+        }
+
+        // The polynomial X^N used when decoding
+        let mut a = vec![Scalar::ZERO; 1024];
+        a.push(Scalar::ONE);
+        let b: Vec<_> = (0..1024).map(|_| Scalar::random(&mut rng)).collect();
+        let expected = euclidean_algorithm_matrix(a.clone(), b.clone(), 512);
+        assert_eq!(half_gcd(&a, &b), expected);
+    }
+
+    #[test]
+    fn systematic_encoding_round_trip() {
+        const POLY_LEN: usize = 64;
+        const BLOCK_SIZE: usize = 1; // Note: This is not used for systematic decoding
+
+        for expansion_factor in [2, 4] {
+            let rs = ReedSolomon::new(POLY_LEN, expansion_factor, BLOCK_SIZE);
+            let data: Vec<_> = (0..POLY_LEN)
+                .map(|i| Scalar::from((i * i) as u64))
+                .collect();
+
+            let codeword = rs.encode_systematic(data.clone()).unwrap();
+            assert_eq!(codeword.len(), rs.codeword_length());
+            assert_eq!(&codeword[..POLY_LEN], &data);
+
+            // Erase every value of the data, along with some of the extension
+            let erasures: Vec<_> = (0..rs.acceptable_num_random_erasures()).collect();
+            let mut partial_codeword = codeword.clone();
+            for index in &erasures {
+                partial_codeword[*index] = Scalar::ZERO;
+            }
+            assert_eq!(
+                rs.decode_systematic(partial_codeword.clone(), erasures.clone())
+                    .unwrap(),
+                data
+            );
+
+            // The vanishing polynomial for the erasures can be computed once and shared
+            let vanishing_poly = rs.systematic_erasure_vanishing_poly(erasures).unwrap();
+            assert_eq!(
+                rs.decode_systematic_with_vanishing_poly(partial_codeword, &vanishing_poly)
+                    .unwrap(),
+                data
+            );
+
+            assert_eq!(rs.decode_systematic(codeword, Vec::new()).unwrap(), data);
+        }
+
+        let rs = ReedSolomon::new(POLY_LEN, 2, BLOCK_SIZE);
+        assert!(matches!(
+            rs.encode_systematic(vec![Scalar::ONE; POLY_LEN + 1]),
+            Err(RSError::DataHasInvalidLength {
+                data_length: 65,
+                expected_data_length: 64
+            })
+        ));
+        // Erasures are reported using the positions that were given, not the permuted positions
+        assert!(matches!(
+            rs.decode_systematic(vec![Scalar::ONE; 2 * POLY_LEN], vec![1, 1]),
+            Err(RSError::DuplicateErasureIndex { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn invalid_random_erasures_are_rejected() {
+        const POLY_LEN: usize = 16;
+        const EXPANSION_FACTOR: usize = 2;
+        const BLOCK_SIZE: usize = 1;
+
+        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let poly_coeff: Vec<_> = (0..POLY_LEN).map(|i| Scalar::from(i as u64)).collect();
+        let codeword = rs.encode(poly_coeff).unwrap();
+
+        assert!(matches!(
+            rs.recover_polynomial_coefficient_random_erasure(codeword.clone(), vec![0, 32]),
+            Err(RSError::InvalidErasureIndex {
+                index: 32,
+                codeword_length: 32
+            })
+        ));
+        assert!(matches!(
+            rs.recover_polynomial_coefficient_random_erasure(codeword.clone(), vec![3, 5, 3]),
+            Err(RSError::DuplicateErasureIndex { index: 3 })
+        ));
+        assert!(matches!(
+            rs.recover_polynomial_coefficient_random_erasure(
+                codeword.clone(),
+                (0..=rs.acceptable_num_random_erasures()).collect()
+            ),
+            Err(RSError::TooManyErasures {
+                num_erasures: 17,
+                max_num_erasures_accepted: 16
+            })
+        ));
+        assert!(matches!(
+            rs.recover_polynomial_coefficient_random_erasure(codeword[1..].to_vec(), vec![0]),
+            Err(RSError::CodewordHasInvalidLength {
+                codeword_length: 31,
+                expected_codeword_length: 32
+            })
+        ));
+    }
 }
@@ -3,7 +3,7 @@ use bls12_381::{ff::Field, group::Group, G1Projective};
 use bls12_381::{g1_batch_normalize, g2_batch_normalize, G2Projective, Scalar};
 use crate_crypto_kzg_multi_open_fk20::commit_key::CommitKey;
 use crate_crypto_kzg_multi_open_fk20::opening_key::OpeningKey;
-use crate_crypto_kzg_multi_open_fk20::{Prover, ProverInput};
+use crate_crypto_kzg_multi_open_fk20::{PrecomputationLevel, Prover, ProverInput};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 pub fn bench_msm(c: &mut Criterion) {
@@ -48,26 +48,47 @@ pub fn bench_compute_proof(c: &mut Criterion) {
 
     const NUMBER_OF_POINTS_PER_PROOF: usize = 64;
 
-    let prover = Prover::new(
-        ck,
-        POLYNOMIAL_LEN,
-        NUMBER_OF_POINTS_PER_PROOF,
-        NUMBER_OF_POINTS_TO_EVALUATE,
-    );
-    let num_proofs = prover.num_proofs();
-    c.bench_function(
-        &format!(
-            "computing proofs with fk20. POLY_SIZE {}, NUM_INPUT_POINTS {}, NUM_PROOFS {}",
-            POLYNOMIAL_LEN, NUMBER_OF_POINTS_PER_PROOF, num_proofs
-        ),
-        |b| {
-            b.iter(|| {
-                prover.compute_multi_opening_proofs(ProverInput::PolyCoeff(polynomial_4096.clone()))
-            })
-        },
-    );
+    for precomputation in PRECOMPUTATION_LEVELS {
+        let prover = Prover::new(
+            ck.clone(),
+            POLYNOMIAL_LEN,
+            NUMBER_OF_POINTS_PER_PROOF,
+            NUMBER_OF_POINTS_TO_EVALUATE,
+            precomputation,
+        );
+        let num_proofs = prover.num_proofs();
+        // The serialized precomputation stores the same points as the precomputation held in memory,
+        // so we use its size to report the memory used.
+        let precomputation_size = prover.precomputation_to_bytes().len();
+        c.bench_function(
+            &format!(
+                "computing proofs with fk20. POLY_SIZE {}, NUM_INPUT_POINTS {}, NUM_PROOFS {}, PRECOMPUTATION {:?} ({} MiB)",
+                POLYNOMIAL_LEN,
+                NUMBER_OF_POINTS_PER_PROOF,
+                num_proofs,
+                precomputation,
+                precomputation_size / (1024 * 1024)
+            ),
+            |b| {
+                b.iter(|| {
+                    prover.compute_multi_opening_proofs(ProverInput::PolyCoeff(
+                        polynomial_4096.clone(),
+                    ))
+                })
+            },
+        );
+    }
 }
 
+// The precomputation levels that we benchmark the prover with.
+const PRECOMPUTATION_LEVELS: [PrecomputationLevel; 5] = [
+    PrecomputationLevel::None,
+    PrecomputationLevel::WindowBits(4),
+    PrecomputationLevel::WindowBits(6),
+    PrecomputationLevel::WindowBits(8),
+    PrecomputationLevel::WindowBits(10),
+];
+
 // We duplicate this to ensure that the version in the src code is only ever compiled with the test feature.
 //
 // This code should never be used outside of benchmarks and tests.
@@ -1,3 +1,4 @@
+mod accumulator;
 mod batch_toeplitz;
 mod cosets;
 mod h_poly;
@@ -9,5 +10,9 @@ mod prover;
 mod toeplitz;
 mod verifier;
 
+pub use accumulator::MultiOpeningAccumulator;
+pub use batch_toeplitz::PrecomputationLevel;
+pub use cosets::reverse_bit_order;
 pub use prover::{FK20Prover as Prover, Input as ProverInput};
+pub(crate) use verifier::compute_powers;
 pub use verifier::FK20Verifier as Verifier;
@@ -0,0 +1,283 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::fk20::verifier::{random_scalar_128, FK20Verifier};
+use bls12_381::{ff::Field, group::Group, lincomb::g1_lincomb, G1Point, G1Projective, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// MultiOpeningAccumulator folds multi-opening proofs into the random linear combinations
+/// used by `FK20Verifier::verify_multi_opening`, so that they can be verified with a single
+/// pairing check without keeping every opening around.
+///
+/// Since the openings are not known up front, the random challenges cannot be computed
+/// using Fiat-Shamir. Instead, each opening is given its own randomizer, which is a
+/// uniformly random 128 bit integer, sampled from the RNG that is passed to `push`.
+///
+/// Note: Accumulators are only meaningful for the verifier that was used to create them.
+#[derive(Debug, Clone, Default)]
+pub struct MultiOpeningAccumulator {
+    /// The position of each commitment in `commitments`, keyed by its compressed form.
+    commitment_indices: HashMap<[u8; 48], usize>,
+    /// The deduplicated commitments.
+    commitments: Vec<G1Point>,
+    /// The sum of the randomizers of the openings for each commitment.
+    commitment_weights: Vec<Scalar>,
+    /// The random linear combinations of the openings on each coset.
+    ///
+    /// This is a BTreeMap, so that `finalize` does not depend on the insertion order.
+    cosets: BTreeMap<u64, CosetSums>,
+    num_openings: usize,
+}
+
+/// The random linear combination of the openings on a single coset.
+#[derive(Debug, Clone)]
+struct CosetSums {
+    /// The random linear combination of the coset evaluations.
+    ///
+    /// These are bit-reversed.
+    coset_evals: Vec<Scalar>,
+    /// The random linear combination of the proofs.
+    proofs: G1Projective,
+}
+
+impl CosetSums {
+    fn new(coset_size: usize) -> Self {
+        Self {
+            coset_evals: vec![Scalar::ZERO; coset_size],
+            proofs: G1Projective::identity(),
+        }
+    }
+
+    fn add(&mut self, other: &CosetSums) {
+        for (sum, eval) in self.coset_evals.iter_mut().zip(&other.coset_evals) {
+            *sum += eval;
+        }
+        self.proofs += other.proofs;
+    }
+}
+
+impl MultiOpeningAccumulator {
+    /// Creates an accumulator with no openings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of openings that have been added to the accumulator.
+    pub fn len(&self) -> usize {
+        self.num_openings
+    }
+
+    /// Returns true if no openings have been added to the accumulator.
+    pub fn is_empty(&self) -> bool {
+        self.num_openings == 0
+    }
+
+    /// Adds the opening of `commitment` on the coset at `coset_index` to the accumulator.
+    ///
+    /// The randomizer for the opening is sampled from `rng`.
+    pub fn push<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        verifier: &FK20Verifier,
+        commitment: G1Point,
+        // This is bit-reversed.
+        coset_index: u64,
+        // These are bit-reversed.
+        coset_evals: &[Scalar],
+        proof: G1Point,
+    ) {
+        let r = random_scalar_128(rng);
+
+        self.add_commitment_weight(commitment, r);
+
+        let coset_size = verifier.opening_key.coset_size;
+        let coset_sums = self
+            .cosets
+            .entry(coset_index)
+            .or_insert_with(|| CosetSums::new(coset_size));
+        for (sum, eval) in coset_sums.coset_evals.iter_mut().zip(coset_evals) {
+            *sum += eval * r;
+        }
+        coset_sums.proofs += proof * r;
+
+        self.num_openings += 1;
+    }
+
+    /// Combines the openings from `other` into this accumulator.
+    ///
+    /// This allows openings to be accumulated on different threads.
+    pub fn merge(&mut self, other: MultiOpeningAccumulator) {
+        for (commitment, weight) in other.commitments.into_iter().zip(other.commitment_weights) {
+            self.add_commitment_weight(commitment, weight);
+        }
+
+        for (coset_index, other_sums) in other.cosets {
+            match self.cosets.get_mut(&coset_index) {
+                Some(coset_sums) => coset_sums.add(&other_sums),
+                None => {
+                    self.cosets.insert(coset_index, other_sums);
+                }
+            }
+        }
+
+        self.num_openings += other.num_openings;
+    }
+
+    /// Verifies all of the openings that have been added to the accumulator,
+    /// using a single pairing check.
+    ///
+    /// An empty accumulator is valid.
+    pub fn finalize(&self, verifier: &FK20Verifier) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        // First compute a random linear combination of the proofs
+        let comm_random_sum_proofs: G1Projective = self
+            .cosets
+            .values()
+            .map(|coset_sums| coset_sums.proofs)
+            .sum();
+
+        // Now compute a random linear combination of the commitments
+        let comm_random_sum_commitments = g1_lincomb(&self.commitments, &self.commitment_weights)
+            .expect("number of commitments and number of weights should be the same");
+
+        // Compute a random linear combination of the interpolation polynomials
+        //
+        // Interpolation is linear, so we only need to interpolate once per coset.
+        let mut random_sum_interpolation_poly = vec![Scalar::ZERO; verifier.opening_key.coset_size];
+        for (coset_index, coset_sums) in &self.cosets {
+            let interpolation_poly =
+                verifier.interpolate_coset(*coset_index, coset_sums.coset_evals.clone());
+            for (sum, coeff) in random_sum_interpolation_poly
+                .iter_mut()
+                .zip(interpolation_poly)
+            {
+                *sum += coeff;
+            }
+        }
+        let comm_random_sum_interpolation_poly = verifier
+            .opening_key
+            .commit_g1(&random_sum_interpolation_poly);
+
+        // The proofs on each coset are weighted by the coset shift
+        let (coset_proofs, coset_shifts_pow_n): (Vec<_>, Vec<_>) = self
+            .cosets
+            .iter()
+            .map(|(coset_index, coset_sums)| {
+                (
+                    coset_sums.proofs,
+                    verifier.coset_shifts_pow_n[*coset_index as usize],
+                )
+            })
+            .unzip();
+        let coset_proofs = bls12_381::g1_batch_normalize(&coset_proofs);
+        let random_weighted_sum_proofs = g1_lincomb(&coset_proofs, &coset_shifts_pow_n)
+            .expect("number of proofs and number of coset shifts should be the same");
+
+        let rl = (comm_random_sum_commitments - comm_random_sum_interpolation_poly)
+            + random_weighted_sum_proofs;
+
+        verifier.check_random_sums(comm_random_sum_proofs, rl)
+    }
+
+    /// Adds `weight` to the weight of `commitment`, adding the commitment if it has not been seen before.
+    fn add_commitment_weight(&mut self, commitment: G1Point, weight: Scalar) {
+        let num_commitments = self.commitments.len();
+        let index = *self
+            .commitment_indices
+            .entry(commitment.to_compressed())
+            .or_insert(num_commitments);
+        if index == num_commitments {
+            self.commitments.push(commitment);
+            self.commitment_weights.push(Scalar::ZERO);
+        }
+        self.commitment_weights[index] += weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiOpeningAccumulator;
+    use crate::{
+        create_insecure_commit_opening_keys,
+        fk20::{
+            batch_toeplitz::PrecomputationLevel,
+            prover::{FK20Prover, Input},
+            verifier::FK20Verifier,
+        },
+    };
+    use bls12_381::{G1Point, Scalar};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn accumulate_and_merge_openings() {
+        let (commit_key, opening_key) = create_insecure_commit_opening_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+        let num_cosets = num_points_to_open / coset_size;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
+        let fk20_verifier = FK20Verifier::new(opening_key, num_points_to_open, num_cosets);
+
+        let mut openings: Vec<(G1Point, u64, Vec<Scalar>, G1Point)> = Vec::new();
+        for seed in 0..2u64 {
+            let data: Vec<_> = (0..poly_len)
+                .map(|i| Scalar::from(i as u64 * (seed + 1)))
+                .collect();
+            let (proofs, cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+            let commitment = fk20.commit(Input::Data(data));
+            for coset_index in [3, 64, 65, 127] {
+                openings.push((
+                    commitment,
+                    coset_index as u64,
+                    cells[coset_index].clone(),
+                    proofs[coset_index],
+                ));
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut accumulate = |openings: &[(G1Point, u64, Vec<Scalar>, G1Point)]| {
+            let mut accumulator = MultiOpeningAccumulator::new();
+            for (commitment, coset_index, coset_evals, proof) in openings {
+                accumulator.push(
+                    &mut rng,
+                    &fk20_verifier,
+                    *commitment,
+                    *coset_index,
+                    coset_evals,
+                    *proof,
+                );
+            }
+            accumulator
+        };
+
+        assert!(MultiOpeningAccumulator::new().finalize(&fk20_verifier));
+
+        let accumulator = accumulate(&openings);
+        assert_eq!(accumulator.len(), openings.len());
+        assert!(accumulator.finalize(&fk20_verifier));
+
+        let (left, right) = openings.split_at(3);
+        let mut accumulator = accumulate(left);
+        accumulator.merge(accumulate(right));
+        assert_eq!(accumulator.len(), openings.len());
+        assert!(accumulator.finalize(&fk20_verifier));
+
+        // Using the proof for the wrong coset should fail, even after merging
+        let mut invalid_opening = openings[1].clone();
+        invalid_opening.3 = openings[2].3;
+        let mut accumulator = accumulate(&openings);
+        accumulator.merge(accumulate(&[invalid_opening]));
+        assert!(!accumulator.finalize(&fk20_verifier));
+    }
+}
@@ -1,8 +1,52 @@
 use crate::fk20::toeplitz::{CirculantMatrix, ToeplitzMatrix};
-use bls12_381::{fixed_base_msm::FixedBaseMSM, g1_batch_normalize, G1Point, G1Projective};
+use bls12_381::{
+    fixed_base_msm::FixedBaseMSM, g1_batch_normalize, lincomb::g1_lincomb, G1Point, G1Projective,
+};
 use polynomial::domain::Domain;
 use rayon::prelude::*;
 
+/// The amount of precomputation to do for the fixed base multi-scalar multiplications
+/// in `BatchToeplitzMatrixVecMul`.
+///
+/// This is a trade-off between storage and computation, where storage grows exponentially
+/// with the window size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecomputationLevel {
+    /// No tables are precomputed and the multi-scalar multiplications are done using `g1_lincomb`.
+    ///
+    /// This uses the least amount of memory, but is the slowest.
+    None,
+    /// Tables are precomputed for a fixed base multi-scalar multiplication with a window
+    /// of the given number of bits.
+    ///
+    /// Each table stores `2^(bits-1)` points for every point in the vector.
+    WindowBits(usize),
+}
+
+impl Default for PrecomputationLevel {
+    fn default() -> Self {
+        PrecomputationLevel::WindowBits(8)
+    }
+}
+
+impl PrecomputationLevel {
+    /// The largest window size that is supported.
+    ///
+    /// Every extra bit doubles the size of the tables. For the mainnet parameters, the tables
+    /// already take up around 1.6GB at this window size.
+    pub const MAX_WINDOW_BITS: usize = 12;
+
+    /// Returns true if the window size is non-zero and at most `MAX_WINDOW_BITS`.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PrecomputationLevel::None => true,
+            PrecomputationLevel::WindowBits(table_bits) => {
+                (1..=Self::MAX_WINDOW_BITS).contains(table_bits)
+            }
+        }
+    }
+}
+
 /// BatchToeplitzMatrixVecMul allows one to compute multiple matrix vector multiplications
 /// and sum them together.
 /// It is optimized for the usecase where:
@@ -14,7 +58,16 @@ pub struct BatchToeplitzMatrixVecMul {
     /// This contains the number of matrix-vector multiplications that
     /// we can do in a batch.
     batch_size: usize,
-    precomputed_fft_vectors: Vec<FixedBaseMSM>,
+    /// The FFT'd vectors, transposed so that each inner vector contains the bases
+    /// used in one of the multi-scalar multiplications.
+    ///
+    /// These are kept so that the precomputation can be serialized.
+    transposed_fft_vectors: Vec<Vec<G1Point>>,
+    /// The fixed base multi-scalar multiplication tables for each of the transposed vectors.
+    ///
+    /// This is `None` if no precomputation was requested, in which case the multi-scalar
+    /// multiplications are done using the transposed vectors directly.
+    precomputed_fft_vectors: Option<Vec<FixedBaseMSM>>,
     // This is the length of the vector that we are multiplying the matrices with.
     // and subsequently will be the length of the final result of the matrix-vector multiplication.
     size_of_vector: usize,
@@ -24,7 +77,7 @@ pub struct BatchToeplitzMatrixVecMul {
 }
 
 impl BatchToeplitzMatrixVecMul {
-    pub fn new(vectors: Vec<Vec<G1Point>>) -> Self {
+    pub fn new(vectors: Vec<Vec<G1Point>>, precomputation: PrecomputationLevel) -> Self {
         let size_of_vector = vectors[0].len();
         let vectors_all_same_length = vectors.iter().all(|v| v.len() == size_of_vector);
         assert!(
@@ -53,24 +106,125 @@ impl BatchToeplitzMatrixVecMul {
 
         let transposed_msm_vectors = transpose(vectors);
 
-        // Configurable parameter to denote the amount of pre-computation one should do
-        // for the fixed base multi-scalar multiplication.
-        //
-        // This is a trade-off between storage and computation, where storage grows exponentially.
-        const TABLE_BITS: usize = 8;
-        let precomputed_table: Vec<_> = transposed_msm_vectors
-            .into_par_iter()
-            .map(|v| FixedBaseMSM::new(v, TABLE_BITS))
-            .collect();
+        let precomputed_table = match precomputation {
+            PrecomputationLevel::None => None,
+            PrecomputationLevel::WindowBits(table_bits) => {
+                assert!(
+                    precomputation.is_valid(),
+                    "expected the window size to be non-zero and at most {} bits",
+                    PrecomputationLevel::MAX_WINDOW_BITS
+                );
+                Some(
+                    transposed_msm_vectors
+                        .par_iter()
+                        .map(|v| FixedBaseMSM::new(v.clone(), table_bits))
+                        .collect(),
+                )
+            }
+        };
 
         BatchToeplitzMatrixVecMul {
             size_of_vector,
             circulant_domain,
+            transposed_fft_vectors: transposed_msm_vectors,
             precomputed_fft_vectors: precomputed_table,
             batch_size,
         }
     }
 
+    /// Serializes the precomputed FFT'd vectors and their fixed base MSM tables.
+    ///
+    /// The layout is the following, where all integers are big-endian:
+    ///  - size_of_vector: 8 bytes
+    ///  - batch_size: 8 bytes
+    ///  - the transposed FFT'd vectors: `2 * size_of_vector` vectors of `batch_size` uncompressed points
+    ///  - num_tables: 8 bytes, which is zero if no precomputation was done and `2 * size_of_vector` otherwise
+    ///  - the fixed base MSM tables: `num_tables` tables, each prefixed with its length in 8 bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend((self.size_of_vector as u64).to_be_bytes());
+        bytes.extend((self.batch_size as u64).to_be_bytes());
+
+        for point in self.transposed_fft_vectors.iter().flatten() {
+            bytes.extend(point.to_uncompressed());
+        }
+
+        let tables = self.precomputed_fft_vectors.as_deref().unwrap_or_default();
+        bytes.extend((tables.len() as u64).to_be_bytes());
+        for table in tables {
+            let table_bytes = table.to_bytes();
+            bytes.extend((table_bytes.len() as u64).to_be_bytes());
+            bytes.extend(table_bytes);
+        }
+
+        bytes
+    }
+
+    /// Deserializes the precomputation that was serialized using `to_bytes`.
+    ///
+    /// `size_of_vector` and `batch_size` are the dimensions that the precomputation is
+    /// expected to have, which are checked before anything is allocated.
+    ///
+    /// Returns `None` if the bytes do not have the expected layout or dimensions.
+    ///
+    /// Note: The points in the fixed base MSM tables are not checked, so this should only
+    /// be used with bytes that come from a trusted source.
+    pub fn from_bytes(mut bytes: &[u8], size_of_vector: usize, batch_size: usize) -> Option<Self> {
+        assert!(
+            size_of_vector.is_power_of_two(),
+            "expected the size of the vectors to be a power of two"
+        );
+        if take_u64(&mut bytes)? != size_of_vector || take_u64(&mut bytes)? != batch_size {
+            return None;
+        }
+        let num_msms = size_of_vector.checked_mul(2)?;
+
+        let vector_size = batch_size.checked_mul(G1Point::uncompressed_size())?;
+        let vectors_bytes = take_bytes(&mut bytes, num_msms.checked_mul(vector_size)?)?;
+        let transposed_fft_vectors = vectors_bytes
+            .chunks_exact(vector_size)
+            .map(|vector_bytes| {
+                vector_bytes
+                    .chunks_exact(G1Point::uncompressed_size())
+                    .map(|point_bytes| {
+                        let point_bytes =
+                            point_bytes.try_into().expect("chunk has the correct size");
+                        Option::from(G1Point::from_uncompressed_unchecked(point_bytes))
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let num_tables = take_u64(&mut bytes)?;
+        if num_tables != 0 && num_tables != num_msms {
+            return None;
+        }
+        let mut precomputed_fft_vectors = Vec::with_capacity(num_tables);
+        for _ in 0..num_tables {
+            let table_len = take_u64(&mut bytes)?;
+            let table = FixedBaseMSM::from_bytes(take_bytes(&mut bytes, table_len)?)?;
+            // The table must have been created from `batch_size` points, since that is
+            // the number of scalars that we will pass to it.
+            if table.num_points() != batch_size {
+                return None;
+            }
+            precomputed_fft_vectors.push(table);
+        }
+
+        if !bytes.is_empty() {
+            return None;
+        }
+
+        Some(BatchToeplitzMatrixVecMul {
+            batch_size,
+            transposed_fft_vectors,
+            precomputed_fft_vectors: (num_tables != 0).then_some(precomputed_fft_vectors),
+            size_of_vector,
+            circulant_domain: Domain::new(num_msms),
+        })
+    }
+
     // Computes the aggregated sum of many Toeplitz matrix-vector multiplications.
     //
     // ie this method computes \sum_{i}^{n} A_i* x_i (where x_i is fixed)
@@ -98,12 +252,22 @@ impl BatchToeplitzMatrixVecMul {
             .collect();
         let msm_scalars = transpose(col_ffts);
 
-        let result: Vec<_> = self
-            .precomputed_fft_vectors
-            .iter()
-            .zip(msm_scalars)
-            .map(|(points, scalars)| points.msm(scalars))
-            .collect();
+        let result: Vec<_> = match &self.precomputed_fft_vectors {
+            Some(tables) => tables
+                .iter()
+                .zip(msm_scalars)
+                .map(|(table, scalars)| table.msm(scalars))
+                .collect(),
+            None => self
+                .transposed_fft_vectors
+                .iter()
+                .zip(msm_scalars)
+                .map(|(points, scalars)| {
+                    g1_lincomb(points, &scalars)
+                        .expect("number of points and scalars should be the same")
+                })
+                .collect(),
+        };
 
         // Once the aggregate circulant matrix-vector multiplication is done, we need to take the first half
         // of the result, as the second half are extra terms that were added due to the fact that the Toeplitz matrices
@@ -113,6 +277,22 @@ impl BatchToeplitzMatrixVecMul {
     }
 }
 
+/// Takes `len` bytes from the start of `bytes`, advancing it past them.
+fn take_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if bytes.len() < len {
+        return None;
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Some(head)
+}
+
+/// Takes a big-endian u64 from the start of `bytes`, advancing it past it.
+fn take_u64(bytes: &mut &[u8]) -> Option<usize> {
+    let value = u64::from_be_bytes(take_bytes(bytes, 8)?.try_into().ok()?);
+    usize::try_from(value).ok()
+}
+
 /// Transposes a 2D matrix
 ///
 /// This function takes a vector of vectors (representing a matrix) and returns its transpose,
@@ -154,7 +334,7 @@ pub(crate) fn transpose<T: Clone>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::fk20::batch_toeplitz::BatchToeplitzMatrixVecMul;
+    use crate::fk20::batch_toeplitz::{BatchToeplitzMatrixVecMul, PrecomputationLevel};
     use crate::fk20::toeplitz::ToeplitzMatrix;
     use bls12_381::group::Group;
     use bls12_381::{g1_batch_normalize, G1Projective, Scalar};
@@ -192,17 +372,98 @@ mod tests {
             toeplitz_matrices.push(ToeplitzMatrix::new(row, col));
         }
 
-        let bm = BatchToeplitzMatrixVecMul::new(vectors_affine);
-        let got_result = bm.sum_matrix_vector_mul(toeplitz_matrices.clone());
-
-        let mut expected_result = vec![G1Projective::identity(); got_result.len()];
-        for (matrix, vector) in toeplitz_matrices.into_iter().zip(vectors) {
+        let mut expected_result = vec![G1Projective::identity(); 4];
+        for (matrix, vector) in toeplitz_matrices.clone().into_iter().zip(vectors) {
             let intermediate_result = matrix.vector_mul_g1(vector);
             for (got, expected) in expected_result.iter_mut().zip(intermediate_result) {
                 *got += expected;
             }
         }
 
-        assert_eq!(expected_result, got_result)
+        for precomputation in [
+            PrecomputationLevel::None,
+            PrecomputationLevel::WindowBits(2),
+            PrecomputationLevel::default(),
+        ] {
+            let bm = BatchToeplitzMatrixVecMul::new(vectors_affine.clone(), precomputation);
+            let got_result = bm.sum_matrix_vector_mul(toeplitz_matrices.clone());
+            assert_eq!(expected_result, got_result)
+        }
+    }
+
+    #[test]
+    fn window_bits_are_bounded() {
+        assert!(PrecomputationLevel::None.is_valid());
+        assert!(PrecomputationLevel::default().is_valid());
+        assert!(PrecomputationLevel::WindowBits(PrecomputationLevel::MAX_WINDOW_BITS).is_valid());
+
+        assert!(!PrecomputationLevel::WindowBits(0).is_valid());
+        assert!(
+            !PrecomputationLevel::WindowBits(PrecomputationLevel::MAX_WINDOW_BITS + 1).is_valid()
+        );
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let num_matrices = 4;
+        let mut toeplitz_matrices = Vec::new();
+        let mut vectors = Vec::new();
+        for i in 0..num_matrices {
+            let col: Vec<_> = (0..4).map(|j| Scalar::from((i + j + 1) as u64)).collect();
+            let row: Vec<_> = (0..4).map(|j| Scalar::from((i * j + 1) as u64)).collect();
+            let vector: Vec<_> = (0..4)
+                .map(|j| G1Projective::generator() * Scalar::from((i + j + 1) as u64))
+                .collect();
+
+            vectors.push(g1_batch_normalize(&vector));
+            toeplitz_matrices.push(ToeplitzMatrix::new(row, col));
+        }
+
+        for precomputation in [PrecomputationLevel::None, PrecomputationLevel::default()] {
+            let bm = BatchToeplitzMatrixVecMul::new(vectors.clone(), precomputation);
+            let bytes = bm.to_bytes();
+            let bm_from_bytes =
+                BatchToeplitzMatrixVecMul::from_bytes(&bytes, bm.size_of_vector, bm.batch_size)
+                    .unwrap();
+
+            assert_eq!(bm_from_bytes.batch_size, bm.batch_size);
+            assert_eq!(bm_from_bytes.size_of_vector, bm.size_of_vector);
+            assert_eq!(
+                bm_from_bytes.sum_matrix_vector_mul(toeplitz_matrices.clone()),
+                bm.sum_matrix_vector_mul(toeplitz_matrices.clone())
+            );
+            assert_eq!(bm_from_bytes.to_bytes(), bytes);
+        }
+
+        let bm = BatchToeplitzMatrixVecMul::new(vectors, PrecomputationLevel::default());
+        let (size_of_vector, batch_size) = (bm.size_of_vector, bm.batch_size);
+        let bytes = bm.to_bytes();
+
+        // Truncated or extended bytes should be rejected
+        assert!(BatchToeplitzMatrixVecMul::from_bytes(
+            &bytes[..bytes.len() - 1],
+            size_of_vector,
+            batch_size
+        )
+        .is_none());
+        let mut extended_bytes = bytes.clone();
+        extended_bytes.push(0);
+        assert!(
+            BatchToeplitzMatrixVecMul::from_bytes(&extended_bytes, size_of_vector, batch_size)
+                .is_none()
+        );
+
+        // Dimensions that differ from the expected ones should be rejected without allocating
+        assert!(
+            BatchToeplitzMatrixVecMul::from_bytes(&bytes, size_of_vector * 2, batch_size).is_none()
+        );
+        let mut huge_batch_size = bytes;
+        huge_batch_size[8..16].fill(0xff);
+        assert!(BatchToeplitzMatrixVecMul::from_bytes(
+            &huge_batch_size,
+            size_of_vector,
+            batch_size
+        )
+        .is_none());
     }
 }
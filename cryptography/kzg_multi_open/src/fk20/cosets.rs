@@ -1,37 +1,8 @@
 use bls12_381::Scalar;
 use polynomial::domain::Domain;
 
-pub(crate) fn reverse_bits(n: usize, bits: u32) -> usize {
-    let mut n = n;
-    let mut r = 0;
-    for _ in 0..bits {
-        r = (r << 1) | (n & 1);
-        n >>= 1;
-    }
-    r
-}
-
-/// Computes log2 of an integer.
-///
-/// Panics if the integer is not a power of two
-pub(crate) fn log2(x: u32) -> u32 {
-    assert!(x > 0 && x.is_power_of_two(), "x must be a power of two.");
-    x.trailing_zeros()
-}
-
-// Taken and modified from: https://github.com/filecoin-project/ec-gpu/blob/bdde768d0613ae546524c5612e2ad576a646e036/ec-gpu-gen/src/fft_cpu.rs#L10C8-L10C18
-pub fn reverse_bit_order<T>(a: &mut [T]) {
-    let n = a.len() as u32;
-    assert!(n.is_power_of_two(), "n must be a power of two");
-    let log_n = log2(n);
-
-    for k in 0..n {
-        let rk = reverse_bits(k as usize, log_n) as u32;
-        if k < rk {
-            a.swap(rk as usize, k as usize);
-        }
-    }
-}
+pub use polynomial::bit_reverse::reverse_bit_order;
+pub(crate) use polynomial::bit_reverse::{log2, reverse_bits};
 
 /// Generate all of the field elements needed to generate the cosets.
 ///
@@ -107,7 +78,7 @@ mod tests {
 
     use crate::fk20::{
         batch_toeplitz::transpose,
-        cosets::{generate_cosets, log2, reverse_bit_order, reverse_bits},
+        cosets::{generate_cosets, reverse_bit_order},
         h_poly::take_every_nth,
     };
 
@@ -290,25 +261,4 @@ mod tests {
 
         assert_eq!(full_subgroup_set, cosets_flattened_set)
     }
-
-    #[test]
-    fn bit_reverse_fuzz() {
-        fn naive_bit_reverse(n: u32, l: u32) -> u32 {
-            assert!(l.is_power_of_two());
-            let num_bits = l.trailing_zeros();
-            if num_bits == 32 {
-                n.reverse_bits()
-            } else {
-                n.reverse_bits() >> (32 - num_bits)
-            }
-        }
-
-        for i in 0..10 {
-            for k in (1..31).map(|exponent| 2u32.pow(exponent)) {
-                let expected = naive_bit_reverse(i, k);
-                let got = reverse_bits(i as usize, log2(k)) as u32;
-                assert_eq!(expected, got)
-            }
-        }
-    }
 }
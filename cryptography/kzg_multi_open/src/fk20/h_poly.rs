@@ -73,6 +73,7 @@ mod tests {
     use crate::{
         create_insecure_commit_opening_keys,
         fk20::{
+            batch_toeplitz::PrecomputationLevel,
             h_poly::{compute_h_poly_commitments, take_every_nth},
             naive,
             prover::FK20Prover,
@@ -104,7 +105,13 @@ mod tests {
 
         // Compute the commitment to the h_polynomials using the method noted in the FK20 paper
         //
-        let fk20 = FK20Prover::new(commit_key, 4096, coset_size, 2 * 4096);
+        let fk20 = FK20Prover::new(
+            commit_key,
+            4096,
+            coset_size,
+            2 * 4096,
+            PrecomputationLevel::default(),
+        );
         let got_comm_h_polys =
             compute_h_poly_commitments(fk20.batch_toeplitz_matrix(), poly, coset_size);
 
@@ -1,5 +1,5 @@
 use crate::commit_key::CommitKey;
-use crate::fk20::batch_toeplitz::BatchToeplitzMatrixVecMul;
+use crate::fk20::batch_toeplitz::{BatchToeplitzMatrixVecMul, PrecomputationLevel};
 use crate::fk20::cosets::{log2, reverse_bit_order, reverse_bits};
 use crate::fk20::h_poly::take_every_nth;
 use bls12_381::group::prime::PrimeCurveAffine;
@@ -59,18 +59,20 @@ impl FK20Prover {
     /// polynomial_bound: The number of coefficients in the polynomial.
     /// points_per_proof: The number of points that a single proof will attest to.
     /// number_of_points_to_open: The total number of points that we want to open a polynomial at.
+    /// precomputation: The amount of precomputation to do for the multi-scalar multiplications.
     pub fn new(
         commit_key: CommitKey,
         polynomial_bound: usize,
         points_per_proof: usize,
         number_of_points_to_open: usize,
+        precomputation: PrecomputationLevel,
     ) -> FK20Prover {
-        assert!(points_per_proof.is_power_of_two());
-        assert!(number_of_points_to_open.is_power_of_two());
-        assert!(number_of_points_to_open > points_per_proof);
-        assert!(polynomial_bound.is_power_of_two());
-        assert!(commit_key.g1s.len() >= polynomial_bound);
-        assert!(commit_key.g1s.len() > points_per_proof);
+        check_parameters(
+            &commit_key,
+            polynomial_bound,
+            points_per_proof,
+            number_of_points_to_open,
+        );
 
         // 1. Compute the SRS vectors that we will multiply the toeplitz matrices by.
         //
@@ -100,8 +102,75 @@ impl FK20Prover {
 
         // Initialize structure that will allow us to do efficient sum of multiple toeplitz matrix
         // vector multiplication, where the vector is fixed.
-        let batch_toeplitz = BatchToeplitzMatrixVecMul::new(srs_vectors);
+        let batch_toeplitz = BatchToeplitzMatrixVecMul::new(srs_vectors, precomputation);
+
+        Self::from_batch_toeplitz(
+            batch_toeplitz,
+            commit_key,
+            polynomial_bound,
+            points_per_proof,
+            number_of_points_to_open,
+        )
+    }
+
+    /// Initialize a FK20 struct using a precomputation that was serialized with
+    /// `precomputation_to_bytes`, instead of recomputing it.
+    ///
+    /// The parameters have the same meaning as in `new` and must be the same as the ones
+    /// used to create the precomputation. The precomputation level is the one that was used
+    /// to create the precomputation.
+    ///
+    /// Returns `None` if the precomputation is malformed or was created for a different
+    /// number of points per proof or polynomial bound.
+    ///
+    /// Note: The precomputation is not checked against the commitment key, so this should
+    /// only be used with bytes that come from a trusted source.
+    pub fn from_precomputation_bytes(
+        commit_key: CommitKey,
+        polynomial_bound: usize,
+        points_per_proof: usize,
+        number_of_points_to_open: usize,
+        precomputation: &[u8],
+    ) -> Option<FK20Prover> {
+        check_parameters(
+            &commit_key,
+            polynomial_bound,
+            points_per_proof,
+            number_of_points_to_open,
+        );
 
+        // The precomputation must have the same dimensions as the one `new` would create.
+        let num_srs_points = commit_key.g1s.len() - points_per_proof;
+        let size_of_vector =
+            ((num_srs_points + points_per_proof - 1) / points_per_proof).next_power_of_two();
+        let batch_toeplitz = BatchToeplitzMatrixVecMul::from_bytes(
+            precomputation,
+            size_of_vector,
+            points_per_proof,
+        )?;
+
+        Some(Self::from_batch_toeplitz(
+            batch_toeplitz,
+            commit_key,
+            polynomial_bound,
+            points_per_proof,
+            number_of_points_to_open,
+        ))
+    }
+
+    /// Serializes the precomputation that `new` does, so that it can be loaded with
+    /// `from_precomputation_bytes`.
+    pub fn precomputation_to_bytes(&self) -> Vec<u8> {
+        self.batch_toeplitz.to_bytes()
+    }
+
+    fn from_batch_toeplitz(
+        batch_toeplitz: BatchToeplitzMatrixVecMul,
+        commit_key: CommitKey,
+        polynomial_bound: usize,
+        points_per_proof: usize,
+        number_of_points_to_open: usize,
+    ) -> FK20Prover {
         // 2. Compute the domains needed to produce the proofs and the evaluations
         //
         let num_proofs = number_of_points_to_open / points_per_proof;
@@ -145,12 +214,31 @@ impl FK20Prover {
         self.number_of_points_to_open / self.coset_size
     }
 
+    /// Converts the `Input` into a polynomial in monomial form.
+    fn input_to_poly_coeff(&self, input: Input) -> PolyCoeff {
+        match input {
+            Input::PolyCoeff(polynomial) => polynomial,
+            Input::Data(mut data) => {
+                reverse_bit_order(&mut data);
+                self.poly_domain.ifft_scalars(data)
+            }
+        }
+    }
+
+    /// Evaluates the `Input` at all of the relevant cosets, without computing any opening proofs.
+    ///
+    /// This returns the same coset evaluations as `compute_multi_opening_proofs`.
+    pub fn compute_coset_evaluations(&self, input: Input) -> Vec<Vec<Scalar>> {
+        let poly_coeff = self.input_to_poly_coeff(input);
+        self.compute_coset_evaluations_poly_coeff(poly_coeff)
+    }
+
     /// Evaluates the polynomial at all of the relevant cosets.
     ///
     /// Instead of evaluating each coset individually, we can evaluate the polynomial
     /// at all of the points we want to open at, and then use reverse bit ordering
     /// to group the evaluations into the relevant cosets.
-    fn compute_coset_evaluations(&self, polynomial: PolyCoeff) -> Vec<Vec<Scalar>> {
+    fn compute_coset_evaluations_poly_coeff(&self, polynomial: PolyCoeff) -> Vec<Vec<Scalar>> {
         let mut evaluations = self.evaluation_domain.fft_scalars(polynomial);
         reverse_bit_order(&mut evaluations);
         evaluations
@@ -164,13 +252,7 @@ impl FK20Prover {
     /// Returning the opening proofs and the corresponding coset evaluations.
     pub fn compute_multi_opening_proofs(&self, input: Input) -> (Vec<G1Point>, Vec<Vec<Scalar>>) {
         // Convert data to polynomial coefficients
-        let poly_coeff = match input {
-            Input::PolyCoeff(polynomial) => polynomial,
-            Input::Data(mut data) => {
-                reverse_bit_order(&mut data);
-                self.poly_domain.ifft_scalars(data)
-            }
-        };
+        let poly_coeff = self.input_to_poly_coeff(input);
 
         self.compute_multi_opening_proofs_poly_coeff(poly_coeff)
     }
@@ -195,7 +277,7 @@ impl FK20Prover {
 
         (
             g1_batch_normalize(&proofs),
-            self.compute_coset_evaluations(polynomial),
+            self.compute_coset_evaluations_poly_coeff(polynomial),
         )
     }
 
@@ -293,6 +375,21 @@ impl FK20Prover {
     }
 }
 
+/// Checks that the parameters used to initialize the FK20 prover are valid.
+fn check_parameters(
+    commit_key: &CommitKey,
+    polynomial_bound: usize,
+    points_per_proof: usize,
+    number_of_points_to_open: usize,
+) {
+    assert!(points_per_proof.is_power_of_two());
+    assert!(number_of_points_to_open.is_power_of_two());
+    assert!(number_of_points_to_open > points_per_proof);
+    assert!(polynomial_bound.is_power_of_two());
+    assert!(commit_key.g1s.len() >= polynomial_bound);
+    assert!(commit_key.g1s.len() > points_per_proof);
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -301,6 +398,7 @@ mod tests {
     use crate::{
         create_insecure_commit_opening_keys,
         fk20::{
+            batch_toeplitz::PrecomputationLevel,
             cosets::{generate_cosets, log2, reverse_bits},
             naive as fk20naive,
             verifier::FK20Verifier,
@@ -320,7 +418,13 @@ mod tests {
         let num_points_to_open = 2 * poly_len;
         let coset_size = 64;
 
-        let fk20 = FK20Prover::new(commit_key, poly_len, coset_size, num_points_to_open);
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
 
         let data: Vec<_> = (0..poly_len).map(|i| Scalar::from(i as u64)).collect();
         let (_, cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
@@ -330,6 +434,108 @@ mod tests {
         assert_eq!(&data, &cells_flattened[..poly_len]);
     }
 
+    #[test]
+    fn coset_evaluations_match_cells_from_multi_opening_proofs() {
+        let (commit_key, _) = create_insecure_commit_opening_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
+
+        let data: Vec<_> = (0..poly_len).map(|i| -Scalar::from(i as u64)).collect();
+        let (_, expected_cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+
+        let cells = fk20.compute_coset_evaluations(Input::Data(data));
+        assert_eq!(cells, expected_cells);
+    }
+
+    #[test]
+    fn precomputation_round_trip() {
+        let (commit_key, _) = create_insecure_commit_opening_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+
+        let fk20 = FK20Prover::new(
+            commit_key.clone(),
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
+        let precomputation = fk20.precomputation_to_bytes();
+
+        let fk20_from_bytes = FK20Prover::from_precomputation_bytes(
+            commit_key.clone(),
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            &precomputation,
+        )
+        .unwrap();
+
+        let data: Vec<_> = (0..poly_len).map(|i| -Scalar::from(i as u64)).collect();
+        assert_eq!(
+            fk20_from_bytes.compute_multi_opening_proofs(Input::Data(data.clone())),
+            fk20.compute_multi_opening_proofs(Input::Data(data))
+        );
+
+        // A precomputation for a different number of points per proof should be rejected
+        assert!(FK20Prover::from_precomputation_bytes(
+            commit_key,
+            poly_len,
+            coset_size * 2,
+            num_points_to_open,
+            &precomputation,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn proofs_do_not_depend_on_precomputation_level() {
+        let (commit_key, _) = create_insecure_commit_opening_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+
+        let data: Vec<_> = (0..poly_len).map(|i| -Scalar::from(i as u64)).collect();
+        let expected = FK20Prover::new(
+            commit_key.clone(),
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        )
+        .compute_multi_opening_proofs(Input::Data(data.clone()));
+
+        for precomputation in [
+            PrecomputationLevel::None,
+            PrecomputationLevel::WindowBits(4),
+        ] {
+            let fk20 = FK20Prover::new(
+                commit_key.clone(),
+                poly_len,
+                coset_size,
+                num_points_to_open,
+                precomputation,
+            );
+            assert_eq!(
+                fk20.compute_multi_opening_proofs(Input::Data(data.clone())),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn smoke_test_prove_verify() {
         let (commit_key, opening_key) = create_insecure_commit_opening_keys();
@@ -339,7 +545,13 @@ mod tests {
         let coset_size = 64;
         let num_cosets = num_points_to_open / coset_size;
 
-        let fk20 = FK20Prover::new(commit_key, poly_len, coset_size, num_points_to_open);
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
         let fk20_verifier = FK20Verifier::new(opening_key, num_points_to_open, num_cosets);
 
         let data: Vec<_> = (0..poly_len).map(|i| Scalar::from(i as u64)).collect();
@@ -357,6 +569,91 @@ mod tests {
             &proofs,
         );
         assert!(is_valid);
+
+        let mut rng = rand::thread_rng();
+        let is_valid = fk20_verifier.verify_multi_opening_with_rng(
+            &mut rng,
+            &[commitment],
+            &vec![0u64; num_cosets],
+            &coset_indices,
+            &cells,
+            &proofs,
+        );
+        assert!(is_valid);
+
+        // Swapping the proofs of two cosets should fail
+        let mut proofs = proofs;
+        proofs.swap(0, 1);
+        let is_valid = fk20_verifier.verify_multi_opening_with_rng(
+            &mut rng,
+            &[commitment],
+            &vec![0u64; num_cosets],
+            &coset_indices,
+            &cells,
+            &proofs,
+        );
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn smoke_test_prove_verify_same_coset() {
+        let (commit_key, opening_key) = create_insecure_commit_opening_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+        let num_cosets = num_points_to_open / coset_size;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            PrecomputationLevel::default(),
+        );
+        let fk20_verifier = FK20Verifier::new(opening_key, num_points_to_open, num_cosets);
+
+        let coset_index = 5;
+        let mut commitments = Vec::new();
+        let mut coset_evals = Vec::new();
+        let mut proofs = Vec::new();
+        for seed in 0..3u64 {
+            let data: Vec<_> = (0..poly_len)
+                .map(|i| Scalar::from(i as u64 * (seed + 1)))
+                .collect();
+            let (poly_proofs, cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+            commitments.push(fk20.commit(Input::Data(data)));
+            coset_evals.push(cells[coset_index].clone());
+            proofs.push(poly_proofs[coset_index]);
+        }
+
+        let is_valid = fk20_verifier.verify_multi_opening_same_coset(
+            &commitments,
+            coset_index as u64,
+            &coset_evals,
+            &proofs,
+        );
+        assert!(is_valid);
+
+        // The general verification method should agree
+        let is_valid = fk20_verifier.verify_multi_opening(
+            &commitments,
+            &[0, 1, 2],
+            &[coset_index as u64; 3],
+            &coset_evals,
+            &proofs,
+        );
+        assert!(is_valid);
+
+        // Swapping the proofs of two openings should fail
+        proofs.swap(0, 1);
+        let is_valid = fk20_verifier.verify_multi_opening_same_coset(
+            &commitments,
+            coset_index as u64,
+            &coset_evals,
+            &proofs,
+        );
+        assert!(!is_valid);
     }
 
     #[test]
@@ -371,7 +668,13 @@ mod tests {
             fk20naive::open_multi_point(&commit_key, &poly, coset_size, 2 * poly_len);
 
         // Compute proofs using optimized FK20 implementation
-        let fk20 = FK20Prover::new(commit_key, poly_len, coset_size, 2 * poly_len);
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            2 * poly_len,
+            PrecomputationLevel::default(),
+        );
         let (got_proofs, got_evaluations) =
             fk20.compute_multi_opening_proofs_poly_coeff(poly.clone());
 
@@ -4,9 +4,11 @@ use crate::{
 };
 use bls12_381::{
     batch_inversion::batch_inverse, ff::Field, g1_batch_normalize, lincomb::g1_lincomb,
-    multi_pairings, reduce_bytes_to_scalar_bias, G1Point, G2Point, G2Prepared, Scalar,
+    multi_pairings, reduce_bytes_to_scalar_bias, G1Point, G1Projective, G2Point, G2Prepared,
+    Scalar,
 };
 use polynomial::{domain::Domain, monomial::poly_add};
+use rand_core::{CryptoRng, RngCore};
 use sha2::{Digest, Sha256};
 use std::mem::size_of;
 
@@ -112,12 +114,70 @@ impl FK20Verifier {
         );
         let r_powers = compute_powers(r, commitment_indices.len());
 
+        self.verify_multi_opening_with_randomizers(
+            deduplicated_commitments,
+            commitment_indices,
+            coset_indices,
+            coset_evals,
+            proofs,
+            &r_powers,
+        )
+    }
+
+    /// Verify multiple multi-opening proofs, using independent 128 bit randomizers sampled
+    /// from `rng` instead of the powers of a Fiat-Shamir challenge.
+    ///
+    /// This avoids hashing all of the inputs, which is a noticeable cost for large batches.
+    /// The randomizers are used in the same multi-scalar multiplications as the powers of the
+    /// challenge, so their shorter length does not make these any cheaper.
+    ///
+    /// Note: This is not deterministic, so `verify_multi_opening` should be used when
+    /// conforming to the spec is required.
+    pub fn verify_multi_opening_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+
+        deduplicated_commitments: &[G1Point],
+        commitment_indices: &[u64],
+
+        // These are bit-reversed.
+        coset_indices: &[u64],
+        // These are bit-reversed.
+        coset_evals: &[Vec<Scalar>],
+        // These are bit-reversed.
+        proofs: &[G1Point],
+    ) -> bool {
+        let randomizers: Vec<_> = (0..commitment_indices.len())
+            .map(|_| random_scalar_128(rng))
+            .collect();
+
+        self.verify_multi_opening_with_randomizers(
+            deduplicated_commitments,
+            commitment_indices,
+            coset_indices,
+            coset_evals,
+            proofs,
+            &randomizers,
+        )
+    }
+
+    /// Verify multiple multi-opening proofs, where the k'th opening is scaled by `randomizers[k]`
+    /// in the random linear combinations.
+    fn verify_multi_opening_with_randomizers(
+        &self,
+        deduplicated_commitments: &[G1Point],
+        commitment_indices: &[u64],
+        coset_indices: &[u64],
+        coset_evals: &[Vec<Scalar>],
+        proofs: &[G1Point],
+        randomizers: &[Scalar],
+    ) -> bool {
         let num_cosets = coset_indices.len();
         let num_unique_commitments = deduplicated_commitments.len();
 
         // First compute a random linear combination of the proofs
-        let comm_random_sum_proofs = g1_lincomb(proofs, &r_powers)
-            .expect("number of proofs and number of r_powers should be the same");
+        let comm_random_sum_proofs = g1_lincomb(proofs, randomizers)
+            .expect("number of proofs and number of randomizers should be the same");
 
         // Now compute a random linear combination of the commitments
         //
@@ -133,7 +193,7 @@ impl FK20Verifier {
             // ie, `i` just means we are looking at G_i
             let commitment_index = commitment_indices[k];
             // We then add the contribution of `r` as a part of that commitments weight.
-            weights[commitment_index as usize] += r_powers[k];
+            weights[commitment_index as usize] += randomizers[k];
         }
         let comm_random_sum_commitments = g1_lincomb(deduplicated_commitments, &weights)
             .expect("number of row_commitments and number of weights should be the same");
@@ -141,21 +201,12 @@ impl FK20Verifier {
         // Compute a random linear combination of the interpolation polynomials
         let mut random_sum_interpolation_poly = Vec::new();
         let coset_evals = coset_evals.to_vec();
-        for (k, mut coset_eval) in coset_evals.into_iter().enumerate() {
-            // Reverse the order, so it matches the fft domain
-            reverse_bit_order(&mut coset_eval);
-
+        for (k, coset_eval) in coset_evals.into_iter().enumerate() {
             // Compute the interpolation polynomial
-            let ifft_scalars = self.coset_domain.ifft_scalars(coset_eval);
-            let inv_coset_shift_pow_n = &self.inv_coset_shifts_pow_n[coset_indices[k] as usize];
-            let ifft_scalars: Vec<_> = ifft_scalars
-                .into_iter()
-                .zip(inv_coset_shift_pow_n)
-                .map(|(scalar, inv_h_k_pow)| scalar * inv_h_k_pow)
-                .collect();
+            let ifft_scalars = self.interpolate_coset(coset_indices[k], coset_eval);
 
             // Scale the interpolation polynomial by the challenge
-            let scale_factor = r_powers[k];
+            let scale_factor = randomizers[k];
             let scaled_interpolation_poly = ifft_scalars
                 .into_iter()
                 .map(|coeff| coeff * scale_factor)
@@ -167,18 +218,117 @@ impl FK20Verifier {
         let comm_random_sum_interpolation_poly =
             self.opening_key.commit_g1(&random_sum_interpolation_poly);
 
-        let mut weighted_r_powers = Vec::with_capacity(num_cosets);
-        for (coset_index, r_power) in coset_indices.iter().zip(r_powers) {
+        let mut weighted_randomizers = Vec::with_capacity(num_cosets);
+        for (coset_index, randomizer) in coset_indices.iter().zip(randomizers) {
             let coset_shift_pow_n = self.coset_shifts_pow_n[*coset_index as usize];
-            weighted_r_powers.push(r_power * coset_shift_pow_n);
+            weighted_randomizers.push(randomizer * coset_shift_pow_n);
         }
-        let random_weighted_sum_proofs = g1_lincomb(proofs, &weighted_r_powers)
-            .expect("number of proofs and number of weighted_r_powers should be the same");
+        let random_weighted_sum_proofs = g1_lincomb(proofs, &weighted_randomizers)
+            .expect("number of proofs and number of weighted_randomizers should be the same");
 
         // TODO: Find a better name for this (use it from specs)
         let rl = (comm_random_sum_commitments - comm_random_sum_interpolation_poly)
             + random_weighted_sum_proofs;
 
+        self.check_random_sums(comm_random_sum_proofs, rl)
+    }
+
+    /// Verify multi-opening proofs, where every proof opens a different commitment on the
+    /// same coset.
+    ///
+    /// This is the case when verifying a column of the extended blob matrix. Since every
+    /// opening is on the same coset, the random linear combination of the interpolation
+    /// polynomials only needs a single inverse FFT, and the random linear combination of the
+    /// proofs only needs to be scaled by a single coset shift.
+    ///
+    /// The k'th proof and coset evaluations are for the k'th commitment.
+    pub fn verify_multi_opening_same_coset(
+        &self,
+        commitments: &[G1Point],
+        // This is bit-reversed.
+        coset_index: u64,
+        // These are bit-reversed.
+        coset_evals: &[Vec<Scalar>],
+        // These are bit-reversed.
+        proofs: &[G1Point],
+    ) -> bool {
+        // Compute random challenges for batching the opening together.
+        //
+        // The challenge is the same as the one computed by `verify_multi_opening`,
+        // when each commitment is only opened once.
+        let num_openings = commitments.len();
+        let commitment_indices: Vec<u64> = (0..num_openings as u64).collect();
+        let coset_indices = vec![coset_index; num_openings];
+        let r = compute_fiat_shamir_challenge(
+            &self.opening_key,
+            commitments,
+            &commitment_indices,
+            &coset_indices,
+            coset_evals,
+            proofs,
+        );
+        let r_powers = compute_powers(r, num_openings);
+
+        // First compute a random linear combination of the proofs
+        let comm_random_sum_proofs = g1_lincomb(proofs, &r_powers)
+            .expect("number of proofs and number of r_powers should be the same");
+
+        // Now compute a random linear combination of the commitments
+        let comm_random_sum_commitments = g1_lincomb(commitments, &r_powers)
+            .expect("number of commitments and number of r_powers should be the same");
+
+        // Compute a random linear combination of the interpolation polynomials
+        //
+        // Interpolation is linear and every polynomial is interpolated over the same coset,
+        // so we can take the random linear combination of the evaluations and interpolate once.
+        let mut random_sum_coset_evals = vec![Scalar::ZERO; self.opening_key.coset_size];
+        for (coset_eval, r_power) in coset_evals.iter().zip(&r_powers) {
+            for (sum, eval) in random_sum_coset_evals.iter_mut().zip(coset_eval) {
+                *sum += eval * r_power;
+            }
+        }
+        let random_sum_interpolation_poly =
+            self.interpolate_coset(coset_index, random_sum_coset_evals);
+        let comm_random_sum_interpolation_poly =
+            self.opening_key.commit_g1(&random_sum_interpolation_poly);
+
+        let coset_shift_pow_n = self.coset_shifts_pow_n[coset_index as usize];
+        let random_weighted_sum_proofs = comm_random_sum_proofs * coset_shift_pow_n;
+
+        let rl = (comm_random_sum_commitments - comm_random_sum_interpolation_poly)
+            + random_weighted_sum_proofs;
+
+        self.check_random_sums(comm_random_sum_proofs, rl)
+    }
+
+    /// Computes the polynomial in monomial form, that interpolates the evaluations
+    /// over the coset at `coset_index`.
+    pub(crate) fn interpolate_coset(
+        &self,
+        // This is bit-reversed.
+        coset_index: u64,
+        // These are bit-reversed.
+        mut coset_evals: Vec<Scalar>,
+    ) -> Vec<Scalar> {
+        // Reverse the order, so it matches the fft domain
+        reverse_bit_order(&mut coset_evals);
+
+        let ifft_scalars = self.coset_domain.ifft_scalars(coset_evals);
+        let inv_coset_shift_pow_n = &self.inv_coset_shifts_pow_n[coset_index as usize];
+        ifft_scalars
+            .into_iter()
+            .zip(inv_coset_shift_pow_n)
+            .map(|(scalar, inv_h_k_pow)| scalar * inv_h_k_pow)
+            .collect()
+    }
+
+    /// Checks the pairing equation for the random linear combination of the proofs,
+    /// and the random linear combination of everything else.
+    pub(crate) fn check_random_sums(
+        &self,
+        comm_random_sum_proofs: G1Projective,
+        rl: G1Projective,
+    ) -> bool {
         let normalized_vectors = g1_batch_normalize(&[comm_random_sum_proofs, rl]);
         let random_sum_proofs = normalized_vectors[0];
         let rl = normalized_vectors[1];
@@ -250,15 +400,25 @@ fn compute_fiat_shamir_challenge(
     // This however does not affect our security guarantees because the bias is negligible given we
     // want a uniformly random 128 bit integer.
 
-    // TODO: computing powers will remove the 128 bit structure, consider generating `n` 128 bit scalars
+    // Note: The powers of this challenge are not 128 bit integers. `FK20Verifier::verify_multi_opening_with_rng`
+    // samples independent 128 bit randomizers instead.
     // Also there is a negligible probably that the scalar is zero, so we do not handle this case here.
     reduce_bytes_to_scalar_bias(result)
 }
 
+/// Samples a uniformly random 128 bit integer, as a scalar.
+///
+/// 128 bits is enough for the randomizers used in batch verification.
+/// See for example, the randomizers section in : https://cr.yp.to/badbatch/badbatch-20120919.pdf
+pub(crate) fn random_scalar_128<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar {
+    Scalar::from_u64s_le(&[rng.next_u64(), rng.next_u64(), 0, 0])
+        .expect("128 bit integer is smaller than the scalar field order")
+}
+
 /// Computes a vector of powers of a given scalar value.
 ///
 /// Example: compute_powers(x, 5) = [1, x, x^2, x^3, x^4]
-fn compute_powers(value: Scalar, num_elements: usize) -> Vec<Scalar> {
+pub(crate) fn compute_powers(value: Scalar, num_elements: usize) -> Vec<Scalar> {
     use bls12_381::ff::Field;
 
     let mut powers = Vec::with_capacity(num_elements);
@@ -1,8 +1,11 @@
 pub mod commit_key;
 pub mod fk20;
 pub mod opening_key;
+pub mod single_point;
 
-pub use fk20::{Prover, ProverInput, Verifier};
+pub use fk20::{
+    reverse_bit_order, MultiOpeningAccumulator, PrecomputationLevel, Prover, ProverInput, Verifier,
+};
 
 #[cfg(test)]
 mod naive;
@@ -1,6 +1,8 @@
 use crate::{commit_key::CommitKey, opening_key::OpeningKey};
 use bls12_381::{multi_pairings, G1Point, G1Projective, G2Point, G2Prepared, Scalar};
-use polynomial::monomial::{lagrange_interpolate, poly_eval, poly_sub, vanishing_poly, PolyCoeff};
+use polynomial::monomial::{
+    lagrange_interpolate, poly_div_by_linear, poly_eval, poly_sub, vanishing_poly, PolyCoeff,
+};
 
 /// This modules contains code to create and verify opening proofs in a naive way.
 /// It is also general, meaning the points we are creating opening proofs
@@ -64,25 +66,6 @@ fn _compute_multi_opening_naive(
     polynomial: &PolyCoeff,
     points: &[Scalar],
 ) -> (G1Point, Vec<Scalar>) {
-    // Divides `self` by x-z using Ruffinis rule
-    fn divide_by_linear(poly: &[Scalar], z: Scalar) -> Vec<Scalar> {
-        let mut quotient: Vec<Scalar> = Vec::with_capacity(poly.len());
-        let mut k = Scalar::from(0u64);
-
-        for coeff in poly.iter().rev() {
-            let t = *coeff + k;
-            quotient.push(t);
-            k = z * t;
-        }
-
-        // Pop off the remainder term
-        quotient.pop();
-
-        // Reverse the results as monomial form stores coefficients starting with lowest degree
-        quotient.reverse();
-        quotient
-    }
-
     let mut evaluations = Vec::new();
     for point in points {
         let evaluation = poly_eval(polynomial, point);
@@ -113,7 +96,7 @@ fn _compute_multi_opening_naive(
 
     let mut quotient_poly = poly_shifted.to_vec().clone();
     for point in points.iter() {
-        quotient_poly = divide_by_linear(&quotient_poly, *point);
+        quotient_poly = poly_div_by_linear(&quotient_poly, *point);
     }
 
     (commit_key.commit_g1(&quotient_poly).into(), evaluations)
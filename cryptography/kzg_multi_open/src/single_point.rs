@@ -0,0 +1,6 @@
+mod barycentric;
+mod prover;
+mod verifier;
+
+pub use prover::SinglePointProver;
+pub use verifier::SinglePointVerifier;
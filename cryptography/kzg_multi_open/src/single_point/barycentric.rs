@@ -0,0 +1,156 @@
+use bls12_381::{batch_inversion::batch_inverse, ff::Field, Scalar};
+use polynomial::domain::Domain;
+
+/// Evaluates a polynomial in lagrange form at the point `z`.
+///
+/// `evaluations` are the evaluations of the polynomial over the points in `domain`,
+/// in the same order as the roots of unity in the domain.
+///
+/// When `z` is not in the domain, we use the barycentric formula:
+///     f(z) = (z^n - 1) / n * \sum_i f_i * \omega_i / (z - \omega_i)
+///
+/// When `z` is in the domain, the evaluation is already known.
+pub(crate) fn evaluate(domain: &Domain, evaluations: &[Scalar], z: Scalar) -> Scalar {
+    assert_eq!(
+        evaluations.len(),
+        domain.roots.len(),
+        "expected as many evaluations as there are points in the domain"
+    );
+
+    if let Some(index) = domain.roots.iter().position(|root| *root == z) {
+        return evaluations[index];
+    }
+
+    // Compute 1 / (z - \omega_i)
+    //
+    // None of these will be zero since `z` is not in the domain.
+    let mut inv_denominators: Vec<_> = domain.roots.iter().map(|root| z - root).collect();
+    batch_inverse(&mut inv_denominators);
+
+    let sum: Scalar = evaluations
+        .iter()
+        .zip(&domain.roots)
+        .zip(inv_denominators)
+        .map(|((f_i, root), inv_denominator)| f_i * root * inv_denominator)
+        .sum();
+
+    let z_pow_n_minus_one = z.pow_vartime([domain.roots.len() as u64]) - Scalar::ONE;
+
+    sum * z_pow_n_minus_one * domain.domain_size_inv
+}
+
+/// Computes the quotient `q(X) = (f(X) - y) / (X - z)` in lagrange form, where `y = f(z)`.
+///
+/// `evaluations` are the evaluations of `f(X)` over the points in `domain`,
+/// in the same order as the roots of unity in the domain.
+///
+/// When `z` is equal to \omega_m, a point in the domain, the quotient cannot be computed
+/// at \omega_m by division. Instead we use the following formula:
+///     q(\omega_m) = \sum_{i != m} (f_i - y) * \omega_i / (\omega_m * (\omega_m - \omega_i))
+pub(crate) fn compute_quotient(
+    domain: &Domain,
+    evaluations: &[Scalar],
+    z: Scalar,
+    y: Scalar,
+) -> Vec<Scalar> {
+    assert_eq!(
+        evaluations.len(),
+        domain.roots.len(),
+        "expected as many evaluations as there are points in the domain"
+    );
+
+    let z_index = domain.roots.iter().position(|root| *root == z);
+
+    // Compute 1 / (\omega_i - z)
+    //
+    // If `z` is in the domain, then one of these denominators will be zero.
+    // We replace it with one, so that we can batch invert, and then compute the
+    // quotient at that point separately.
+    let mut inv_denominators: Vec<_> = domain
+        .roots
+        .iter()
+        .map(|root| if *root == z { Scalar::ONE } else { root - z })
+        .collect();
+    batch_inverse(&mut inv_denominators);
+
+    let mut quotient: Vec<_> = evaluations
+        .iter()
+        .zip(inv_denominators)
+        .map(|(f_i, inv_denominator)| (f_i - y) * inv_denominator)
+        .collect();
+
+    if let Some(m) = z_index {
+        // Note: (f_i - y) / (\omega_m - \omega_i) = -q(\omega_i) for i != m, so the formula
+        // above simplifies to:
+        //     q(\omega_m) = -1/\omega_m * \sum_{i != m} q(\omega_i) * \omega_i
+        let sum: Scalar = quotient
+            .iter()
+            .zip(&domain.roots)
+            .enumerate()
+            .filter(|(i, _)| *i != m)
+            .map(|(_, (q_i, root))| q_i * root)
+            .sum();
+        let inv_z = z.invert().expect("roots of unity are non-zero");
+        quotient[m] = -sum * inv_z;
+    }
+
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use bls12_381::Scalar;
+    use polynomial::{
+        domain::Domain,
+        monomial::{poly_div_by_linear, poly_eval},
+    };
+
+    use super::{compute_quotient, evaluate};
+
+    #[test]
+    fn barycentric_evaluation_matches_monomial_evaluation() {
+        let domain = Domain::new(32);
+        let poly_coeff: Vec<_> = (0..32).map(|i| -Scalar::from(i * 7 + 3)).collect();
+        let evaluations = domain.fft_scalars(poly_coeff.clone());
+
+        // Point outside of the domain
+        let z = Scalar::from(123456789u64);
+        assert_eq!(
+            evaluate(&domain, &evaluations, z),
+            poly_eval(&poly_coeff, &z)
+        );
+
+        // Points inside of the domain
+        for root in &domain.roots {
+            assert_eq!(
+                evaluate(&domain, &evaluations, *root),
+                poly_eval(&poly_coeff, root)
+            );
+        }
+    }
+
+    #[test]
+    fn quotient_in_lagrange_form_matches_monomial_division() {
+        let domain = Domain::new(32);
+        let poly_coeff: Vec<_> = (0..32).map(|i| -Scalar::from(i * 7 + 3)).collect();
+        let evaluations = domain.fft_scalars(poly_coeff.clone());
+
+        let points = [
+            Scalar::from(123456789u64),
+            domain.roots[0],
+            domain.roots[13],
+        ];
+        for z in points {
+            let y = evaluate(&domain, &evaluations, z);
+
+            let quotient_lagrange = compute_quotient(&domain, &evaluations, z, y);
+            let expected_quotient = poly_div_by_linear(&poly_coeff, z);
+
+            assert_eq!(
+                domain.fft_scalars(expected_quotient),
+                quotient_lagrange,
+                "quotient is incorrect"
+            );
+        }
+    }
+}
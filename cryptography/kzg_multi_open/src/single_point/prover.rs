@@ -0,0 +1,183 @@
+use bls12_381::{G1Point, Scalar};
+use polynomial::domain::Domain;
+
+use crate::{
+    commit_key::CommitKey,
+    fk20::{reverse_bit_order, ProverInput as Input},
+};
+
+use super::barycentric::{compute_quotient, evaluate};
+
+/// SinglePointProver initializes all of the components needed to compute a KZG opening
+/// proof for a polynomial at a single, arbitrary point.
+///
+/// The prover works over the polynomial in lagrange form, so that it can operate directly
+/// on the data that was committed to, without needing to interpolate it first.
+#[derive(Debug)]
+pub struct SinglePointProver {
+    /// Commitment key used for committing to the polynomial and the quotient
+    /// in monomial form.
+    commit_key: CommitKey,
+    /// Domain over which the polynomial is represented in lagrange form.
+    domain: Domain,
+}
+
+impl SinglePointProver {
+    /// Initialize a SinglePointProver with the given parameters.
+    ///
+    /// commit_key: The commitment key used to commit to polynomials.
+    /// polynomial_bound: The number of coefficients in the polynomial.
+    pub fn new(commit_key: CommitKey, polynomial_bound: usize) -> SinglePointProver {
+        assert!(polynomial_bound.is_power_of_two());
+        assert!(commit_key.g1s.len() >= polynomial_bound);
+
+        SinglePointProver {
+            commit_key,
+            domain: Domain::new(polynomial_bound),
+        }
+    }
+
+    /// Converts the `Input` into the evaluations of the polynomial over the domain,
+    /// in the same order as the roots of unity in the domain.
+    fn to_lagrange_form(&self, input: Input) -> Vec<Scalar> {
+        match input {
+            Input::PolyCoeff(poly_coeff) => self.domain.fft_scalars(poly_coeff),
+            Input::Data(mut data) => {
+                // The data is the evaluations of the polynomial in bit-reversed order.
+                reverse_bit_order(&mut data);
+                data
+            }
+        }
+    }
+
+    /// Commit to the `Input` that we will be creating opening proofs over.
+    pub fn commit(&self, input: Input) -> G1Point {
+        let evaluations = self.to_lagrange_form(input);
+        let poly_coeff = self.domain.ifft_scalars(evaluations);
+        self.commit_key.commit_g1(&poly_coeff).into()
+    }
+
+    /// Computes an opening proof for the `Input` at the point `z`.
+    ///
+    /// Returns the opening proof and the evaluation of the polynomial at `z`.
+    pub fn compute_opening_proof(&self, input: Input, z: Scalar) -> (G1Point, Scalar) {
+        let evaluations = self.to_lagrange_form(input);
+
+        let y = evaluate(&self.domain, &evaluations, z);
+
+        // Compute the quotient q(X) = (f(X) - f(z)) / (X - z) in lagrange form
+        let quotient = compute_quotient(&self.domain, &evaluations, z, y);
+
+        // Commit to the quotient in monomial form.
+        let quotient_coeff = self.domain.ifft_scalars(quotient);
+        let proof = self.commit_key.commit_g1(&quotient_coeff).into();
+
+        (proof, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bls12_381::{G1Point, Scalar};
+    use polynomial::monomial::{poly_div_by_linear, poly_eval};
+
+    use crate::{
+        create_insecure_commit_opening_keys,
+        fk20::{reverse_bit_order, ProverInput},
+        single_point::SinglePointVerifier,
+    };
+
+    use super::SinglePointProver;
+
+    fn test_polynomial() -> Vec<Scalar> {
+        (0..4096).map(|i| -Scalar::from(i as u64 * 3 + 1)).collect()
+    }
+
+    #[test]
+    fn smoke_test_prove_verify() {
+        let (ck, vk) = create_insecure_commit_opening_keys();
+        let poly_len = 4096;
+        let prover = SinglePointProver::new(ck, poly_len);
+        let verifier = SinglePointVerifier::new(vk, poly_len);
+
+        let poly_coeff = test_polynomial();
+        let commitment = prover.commit(ProverInput::PolyCoeff(poly_coeff.clone()));
+
+        let points = [
+            Scalar::from(1234u64),
+            prover.domain.roots[0],
+            prover.domain.roots[17],
+        ];
+        for z in points {
+            let (proof, y) =
+                prover.compute_opening_proof(ProverInput::PolyCoeff(poly_coeff.clone()), z);
+            assert_eq!(y, poly_eval(&poly_coeff, &z));
+            assert!(verifier.verify(commitment, z, y, proof));
+            assert!(!verifier.verify(commitment, z, y + Scalar::from(1u64), proof));
+        }
+    }
+
+    #[test]
+    fn check_consistency_of_proofs_against_monomial_division() {
+        let (ck, _) = create_insecure_commit_opening_keys();
+        let poly_len = 4096;
+        let prover = SinglePointProver::new(ck.clone(), poly_len);
+
+        let poly_coeff = test_polynomial();
+
+        // Create the data from the polynomial, in bit-reversed order.
+        let mut data = prover.domain.fft_scalars(poly_coeff.clone());
+        reverse_bit_order(&mut data);
+
+        let points = [
+            Scalar::from(1234u64),
+            prover.domain.roots[0],
+            prover.domain.roots[17],
+        ];
+        for z in points {
+            let (proof, y) = prover.compute_opening_proof(ProverInput::Data(data.clone()), z);
+
+            // Compute the proof in monomial form
+            let expected_quotient = poly_div_by_linear(&poly_coeff, z);
+            let expected_proof: G1Point = ck.commit_g1(&expected_quotient).into();
+
+            assert_eq!(proof, expected_proof);
+            assert_eq!(y, poly_eval(&poly_coeff, &z));
+        }
+
+        let commitment = prover.commit(ProverInput::Data(data));
+        let expected_commitment: G1Point = ck.commit_g1(&poly_coeff).into();
+        assert_eq!(commitment, expected_commitment);
+    }
+
+    #[test]
+    fn smoke_test_batch_verify() {
+        let (ck, vk) = create_insecure_commit_opening_keys();
+        let poly_len = 4096;
+        let prover = SinglePointProver::new(ck, poly_len);
+        let verifier = SinglePointVerifier::new(vk, poly_len);
+
+        let mut commitments = Vec::new();
+        let mut zs = Vec::new();
+        let mut ys = Vec::new();
+        let mut proofs = Vec::new();
+        for i in 0..4 {
+            let poly_coeff: Vec<_> = test_polynomial()
+                .into_iter()
+                .map(|coeff| coeff + Scalar::from(i as u64))
+                .collect();
+            let z = Scalar::from(i as u64 + 100);
+
+            commitments.push(prover.commit(ProverInput::PolyCoeff(poly_coeff.clone())));
+            let (proof, y) = prover.compute_opening_proof(ProverInput::PolyCoeff(poly_coeff), z);
+            zs.push(z);
+            ys.push(y);
+            proofs.push(proof);
+        }
+
+        assert!(verifier.verify_batch(&commitments, &zs, &ys, &proofs));
+
+        ys[2] += Scalar::from(1u64);
+        assert!(!verifier.verify_batch(&commitments, &zs, &ys, &proofs));
+    }
+}
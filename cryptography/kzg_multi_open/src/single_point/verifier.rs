@@ -0,0 +1,179 @@
+use bls12_381::{
+    g1_batch_normalize, lincomb::g1_lincomb, multi_pairings, reduce_bytes_to_scalar, G1Point,
+    G1Projective, G2Prepared, Scalar,
+};
+use polynomial::{domain::Domain, monomial::poly_eval};
+use sha2::{Digest, Sha256};
+use std::mem::size_of;
+
+use crate::{
+    fk20::{compute_powers, reverse_bit_order, ProverInput as Input},
+    opening_key::OpeningKey,
+};
+
+use super::barycentric::evaluate;
+
+/// SinglePointVerifier initializes all of the components needed to verify KZG opening
+/// proofs that were created using the SinglePointProver.
+#[derive(Debug)]
+pub struct SinglePointVerifier {
+    pub opening_key: OpeningKey,
+    /// Domain over which the polynomial is represented in lagrange form.
+    domain: Domain,
+    // Pre-computations for the verification algorithm
+    //
+    // [s]_2
+    g2_s: G2Prepared,
+    // [-1]_2
+    neg_g2_gen: G2Prepared,
+}
+
+impl SinglePointVerifier {
+    /// Initialize a SinglePointVerifier with the given parameters.
+    ///
+    /// opening_key: The opening key used to verify opening proofs.
+    /// polynomial_bound: The number of coefficients in the polynomial.
+    pub fn new(opening_key: OpeningKey, polynomial_bound: usize) -> Self {
+        assert!(polynomial_bound.is_power_of_two());
+        assert!(
+            opening_key.g2s.len() >= 2,
+            "need at least two g2 points to verify a single point opening"
+        );
+
+        let g2_s = G2Prepared::from(opening_key.g2s[1]);
+        let neg_g2_gen = G2Prepared::from(-opening_key.g2_gen());
+
+        Self {
+            opening_key,
+            domain: Domain::new(polynomial_bound),
+            g2_s,
+            neg_g2_gen,
+        }
+    }
+
+    /// Evaluates the polynomial represented by the `Input` at the point `z`.
+    pub fn evaluate(&self, input: Input, z: Scalar) -> Scalar {
+        match input {
+            Input::PolyCoeff(poly_coeff) => poly_eval(&poly_coeff, &z),
+            Input::Data(mut data) => {
+                // The data is the evaluations of the polynomial in bit-reversed order.
+                reverse_bit_order(&mut data);
+                evaluate(&self.domain, &data, z)
+            }
+        }
+    }
+
+    /// Verifies a proof attesting that the committed polynomial evaluates to `y` at `z`.
+    ///
+    /// The conventional pairing equation is:
+    ///     e([f(s) - y]_1, [1]_2) == e(proof, [s - z]_2)
+    ///
+    /// We rearrange it so that the G2 points are fixed and can be precomputed:
+    ///     e(proof, [s]_2) * e([f(s) - y]_1 + z * proof, [-1]_2) == 1
+    pub fn verify(&self, commitment: G1Point, z: Scalar, y: Scalar, proof: G1Point) -> bool {
+        let rhs: G1Point =
+            (G1Projective::from(commitment) - self.opening_key.g1s[0] * y + proof * z).into();
+
+        multi_pairings(&[(&proof, &self.g2_s), (&rhs, &self.neg_g2_gen)])
+    }
+
+    /// Verifies multiple proofs, each attesting that a committed polynomial evaluates to `ys[i]` at `zs[i]`.
+    ///
+    /// This is done by taking a random linear combination of the individual pairing equations,
+    /// so that only one pairing check is needed:
+    ///     e(\sum r^i proof_i, [s]_2) == e(\sum r^i (C_i - [y_i]_1 + z_i proof_i), [1]_2)
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#verify_kzg_proof_batch
+    pub fn verify_batch(
+        &self,
+        commitments: &[G1Point],
+        zs: &[Scalar],
+        ys: &[Scalar],
+        proofs: &[G1Point],
+    ) -> bool {
+        assert!(
+            commitments.len() == zs.len()
+                && commitments.len() == ys.len()
+                && commitments.len() == proofs.len(),
+            "expected the same number of commitments, points, evaluations and proofs"
+        );
+
+        let r = compute_fiat_shamir_challenge(self.domain.roots.len(), commitments, zs, ys, proofs);
+        let r_powers = compute_powers(r, commitments.len());
+
+        // First compute a random linear combination of the proofs
+        let proof_lincomb = g1_lincomb(proofs, &r_powers)
+            .expect("number of proofs and number of r_powers should be the same");
+
+        // Compute a random linear combination of the proofs, where each proof
+        // is also weighted by its opening point
+        let weighted_r_powers: Vec<_> = zs
+            .iter()
+            .zip(&r_powers)
+            .map(|(z, r_power)| z * r_power)
+            .collect();
+        let proof_z_lincomb = g1_lincomb(proofs, &weighted_r_powers)
+            .expect("number of proofs and number of weighted_r_powers should be the same");
+
+        // Compute a random linear combination of the commitments minus their evaluations.
+        //
+        // Instead of computing C_i - [y_i]_1 for each commitment, we note that
+        // \sum r^i (C_i - [y_i]_1) = \sum r^i C_i - [\sum r^i y_i]_1
+        let comm_lincomb = g1_lincomb(commitments, &r_powers)
+            .expect("number of commitments and number of r_powers should be the same");
+        let random_sum_ys: Scalar = ys
+            .iter()
+            .zip(&r_powers)
+            .map(|(y, r_power)| y * r_power)
+            .sum();
+        let comm_minus_y_lincomb = comm_lincomb - self.opening_key.g1s[0] * random_sum_ys;
+
+        let normalized_vectors =
+            g1_batch_normalize(&[proof_lincomb, comm_minus_y_lincomb + proof_z_lincomb]);
+        let proof_lincomb = normalized_vectors[0];
+        let rhs = normalized_vectors[1];
+
+        multi_pairings(&[(&proof_lincomb, &self.g2_s), (&rhs, &self.neg_g2_gen)])
+    }
+}
+
+/// Computes a random challenge which will allow us to verify multiple opening proofs at once.
+///
+/// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#verify_kzg_proof_batch
+#[allow(clippy::manual_slice_size_calculation)]
+fn compute_fiat_shamir_challenge(
+    polynomial_bound: usize,
+    commitments: &[G1Point],
+    zs: &[Scalar],
+    ys: &[Scalar],
+    proofs: &[G1Point],
+) -> Scalar {
+    const DOMAIN_SEP: &str = "RCKZGBATCH___V1_";
+    let hash_input_size = DOMAIN_SEP.len()
+            + size_of::<u64>() // polynomial bound
+            + size_of::<u64>() // num commitments
+            + commitments.len() * G1Point::compressed_size()
+            + zs.len() * size_of::<Scalar>()
+            + ys.len() * size_of::<Scalar>()
+            + proofs.len() * G1Point::compressed_size();
+
+    let mut hash_input: Vec<u8> = Vec::with_capacity(hash_input_size);
+
+    hash_input.extend(DOMAIN_SEP.as_bytes());
+    hash_input.extend((polynomial_bound as u64).to_be_bytes());
+    hash_input.extend((commitments.len() as u64).to_be_bytes());
+
+    for k in 0..commitments.len() {
+        hash_input.extend(commitments[k].to_compressed());
+        hash_input.extend(zs[k].to_bytes_be());
+        hash_input.extend(ys[k].to_bytes_be());
+        hash_input.extend(proofs[k].to_compressed());
+    }
+
+    assert_eq!(hash_input.len(), hash_input_size);
+    let mut hasher = Sha256::new();
+    hasher.update(hash_input);
+    let result: [u8; 32] = hasher.finalize().into();
+
+    reduce_bytes_to_scalar(result)
+}
@@ -1,2 +1,3 @@
+pub mod bit_reverse;
 pub mod domain;
 pub mod monomial;
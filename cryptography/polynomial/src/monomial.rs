@@ -1,6 +1,8 @@
 use bls12_381::ff::Field;
 use bls12_381::Scalar;
 
+use crate::domain::Domain;
+
 // This file contains methods on a polynomial in coefficient form.
 
 /// A polynomial in monomial form where the lowest degree term is first
@@ -49,9 +51,23 @@ pub fn poly_eval(poly: &PolyCoeff, value: &Scalar) -> Scalar {
     result
 }
 
+/// Below this many coefficients, multiplying and dividing polynomials naively is faster
+/// than using FFTs.
+const FFT_THRESHOLD: usize = 64;
+
 /// For two polynomials, `f(x)` and `g(x)`, this method computes
 /// the result of `f(x) * g(x)` and returns the result.
+///
+/// Large polynomials are multiplied using FFTs, which takes O(n log n) operations.
 pub fn poly_mul(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) >= FFT_THRESHOLD {
+        let domain = Domain::new(a.len() + b.len() - 1);
+        return poly_mul_fft(&domain, a, b);
+    }
+
     let mut result = vec![Scalar::ZERO; a.len() + b.len() - 1];
 
     for (i, a_coeff) in a.iter().enumerate() {
@@ -63,6 +79,154 @@ pub fn poly_mul(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
     result
 }
 
+/// Multiplies two polynomials by evaluating them over the domain, multiplying the
+/// evaluations and interpolating the result.
+///
+/// The domain must have at least as many points as the product has coefficients.
+fn poly_mul_fft(domain: &Domain, a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
+    let product_len = a.len() + b.len() - 1;
+    let a_eval = domain.fft_scalars(a);
+    let b_eval = domain.fft_scalars(b);
+    let product_eval = a_eval.into_iter().zip(b_eval).map(|(a, b)| a * b).collect();
+
+    let mut product = domain.ifft_scalars(product_eval);
+    product.truncate(product_len);
+    product
+}
+
+/// Given a polynomial `f(x)` and a scalar `z`, this method will compute
+/// the quotient `q(x)` such that `f(x) = q(x) * (x - z) + f(z)`.
+///
+/// The remainder `f(z)` is discarded, which means that the result is
+/// also equal to `(f(x) - f(z)) / (x - z)`.
+///
+// This uses Ruffini's rule (synthetic division).
+pub fn poly_div_by_linear(poly: &[Scalar], z: Scalar) -> PolyCoeff {
+    if poly.is_empty() {
+        return Vec::new();
+    }
+
+    let mut quotient: Vec<Scalar> = Vec::with_capacity(poly.len());
+    let mut k = Scalar::ZERO;
+
+    for coeff in poly.iter().rev() {
+        let t = *coeff + k;
+        quotient.push(t);
+        k = z * t;
+    }
+
+    // Pop off the remainder term
+    quotient.pop();
+
+    // Reverse the results as monomial form stores coefficients starting with lowest degree
+    quotient.reverse();
+    quotient
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes the quotient `q(x)`
+/// and the remainder `r(x)` such that `f(x) = q(x) * g(x) + r(x)`, where the degree
+/// of `r(x)` is less than the degree of `g(x)`.
+///
+/// The returned polynomials have no trailing zero coefficients, so the zero polynomial
+/// is returned as an empty vector.
+///
+/// Large polynomials are divided using FFTs, which takes O(n log n) operations.
+///
+/// Panics if `g(x)` is the zero polynomial.
+pub fn poly_div_rem(dividend: &[Scalar], divisor: &[Scalar]) -> (PolyCoeff, PolyCoeff) {
+    let divisor = &divisor[..poly_len_without_trailing_zeros(divisor)];
+    let leading_coeff_inv = divisor
+        .last()
+        .expect("cannot divide by the zero polynomial")
+        .invert()
+        .expect("the leading coefficient is not zero");
+
+    let mut remainder = dividend[..poly_len_without_trailing_zeros(dividend)].to_vec();
+    if remainder.len() < divisor.len() {
+        return (Vec::new(), remainder);
+    }
+    let quotient_len = remainder.len() - divisor.len() + 1;
+    if quotient_len.min(divisor.len()) >= FFT_THRESHOLD {
+        return poly_div_rem_fft(&remainder, divisor);
+    }
+
+    // Long division, cancelling the leading term of the remainder on each step
+    let mut quotient = vec![Scalar::ZERO; quotient_len];
+    for i in (0..quotient.len()).rev() {
+        let coeff = remainder[i + divisor.len() - 1] * leading_coeff_inv;
+        for (j, divisor_coeff) in divisor.iter().enumerate() {
+            remainder[i + j] -= coeff * divisor_coeff;
+        }
+        quotient[i] = coeff;
+    }
+
+    remainder.truncate(poly_len_without_trailing_zeros(
+        &remainder[..divisor.len() - 1],
+    ));
+    (quotient, remainder)
+}
+
+/// Computes the same result as `poly_div_rem`, for a dividend and divisor without trailing zeros,
+/// where the divisor has at most as many coefficients as the dividend.
+///
+// Reversing the coefficients of `f(x) = q(x) * g(x) + r(x)` gives
+// `rev(f)(x) = rev(q)(x) * rev(g)(x) + x^(deg f - deg r) * rev(r)(x)`, so the reversed quotient
+// is `rev(f)(x) / rev(g)(x) mod x^(deg q + 1)`. The constant term of `rev(g)(x)` is the leading
+// coefficient of `g(x)`, so this inverse power series exists.
+fn poly_div_rem_fft(dividend: &[Scalar], divisor: &[Scalar]) -> (PolyCoeff, PolyCoeff) {
+    let quotient_len = dividend.len() - divisor.len() + 1;
+
+    let reversed_dividend = dividend.iter().rev().take(quotient_len).copied().collect();
+    let reversed_divisor: Vec<_> = divisor.iter().rev().take(quotient_len).copied().collect();
+    let reversed_divisor_inv = poly_inv_power_series(&reversed_divisor, quotient_len);
+
+    let mut quotient = poly_mul(reversed_dividend, reversed_divisor_inv);
+    quotient.truncate(quotient_len);
+    quotient.reverse();
+
+    // The remainder has a smaller degree than the divisor, so we only need the lower
+    // coefficients of `f(x) - q(x) * g(x)`.
+    let mut remainder = poly_mul(quotient.clone(), divisor.to_vec());
+    remainder.truncate(divisor.len() - 1);
+    let mut remainder = poly_sub(dividend[..divisor.len() - 1].to_vec(), remainder);
+    remainder.truncate(poly_len_without_trailing_zeros(&remainder));
+
+    (quotient, remainder)
+}
+
+/// Given a polynomial `f(x)` with a non-zero constant term, this method computes the
+/// polynomial `g(x)` with `num_coeffs` coefficients, such that `f(x) * g(x) = 1 mod x^num_coeffs`.
+///
+// This uses Newton iteration, which doubles the number of correct coefficients in each step.
+fn poly_inv_power_series(poly: &[Scalar], num_coeffs: usize) -> PolyCoeff {
+    let constant_inv = poly[0]
+        .invert()
+        .expect("the constant term should not be zero");
+
+    let mut inverse = vec![constant_inv];
+    while inverse.len() < num_coeffs {
+        let len = (2 * inverse.len()).min(num_coeffs);
+
+        // g(x) = g(x) * (2 - f(x) * g(x)) mod x^len
+        let truncated_poly = poly[..len.min(poly.len())].to_vec();
+        let mut error = poly_mul(truncated_poly, inverse.clone());
+        error.truncate(len);
+        let error = poly_sub(vec![Scalar::from(2u64)], error);
+
+        inverse = poly_mul(inverse, error);
+        inverse.truncate(len);
+    }
+
+    inverse
+}
+
+/// Returns the number of coefficients in the polynomial, ignoring any trailing zero coefficients.
+fn poly_len_without_trailing_zeros(poly: &[Scalar]) -> usize {
+    poly.iter()
+        .rposition(|coeff| *coeff != Scalar::ZERO)
+        .map_or(0, |index| index + 1)
+}
+
 /// Given a list of points, this method will compute the polynomial
 /// Z(x) which is equal to zero when evaluated at each point.
 ///
@@ -75,6 +239,39 @@ pub fn vanishing_poly(roots: &[Scalar]) -> Vec<Scalar> {
     poly
 }
 
+/// Computes the same polynomial as `vanishing_poly`, using a subproduct tree.
+///
+/// The leaves of the tree are the vanishing polynomials for small groups of roots, and
+/// each node is the product of its children. The products are computed using FFTs, so this
+/// takes O(n log^2 n) operations instead of the O(n^2) operations needed by `vanishing_poly`.
+pub fn vanishing_poly_subproduct_tree(roots: &[Scalar]) -> PolyCoeff {
+    // Below this many roots, the naive method is faster than an FFT.
+    const LEAF_SIZE: usize = 32;
+
+    let mut layer: Vec<PolyCoeff> = roots.chunks(LEAF_SIZE).map(vanishing_poly).collect();
+    if layer.is_empty() {
+        return vanishing_poly(roots);
+    }
+
+    while layer.len() > 1 {
+        // The product of two polynomials with `max_len` coefficients has `2 * max_len - 1`
+        // coefficients, so we use a domain that is large enough to evaluate it on.
+        let max_len = layer.iter().map(Vec::len).max().unwrap_or(0);
+        let domain = Domain::new(2 * max_len - 1);
+
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => poly_mul_fft(&domain, a.clone(), b.clone()),
+                [a] => a.clone(),
+                _ => unreachable!("chunks of two have one or two elements"),
+            })
+            .collect();
+    }
+
+    layer.pop().expect("the layer has one polynomial")
+}
+
 /// Interpolates a set of points to a given polynomial in monomial form.
 ///
 /// Given a list of points (x_i, y_i), this method will return the lowest degree polynomial
@@ -228,6 +425,94 @@ mod tests {
         assert_eq!(poly_mul(a, b), expected);
     }
 
+    #[test]
+    fn polynomial_division_by_linear() {
+        // f(x) = 4 + 13x + 22x^2 + 15x^3 = (1 + 2x + 3x^2)(4 + 5x)
+        let poly = vec![
+            Scalar::from(4),
+            Scalar::from(13),
+            Scalar::from(22),
+            Scalar::from(15),
+        ];
+        let z = Scalar::from(7u64);
+
+        let quotient = poly_div_by_linear(&poly, z);
+        assert_eq!(quotient.len(), poly.len() - 1);
+
+        // Check that f(x) - f(z) = q(x) * (x - z)
+        let f_z = poly_eval(&poly, &z);
+        let expected = poly_sub(poly, vec![f_z]);
+        let got = poly_mul(quotient, vec![-z, Scalar::from(1u64)]);
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn polynomial_division_with_remainder() {
+        // f(x) = 5 + 13x + 22x^2 + 15x^3 = (1 + 2x + 3x^2)(4 + 5x) + 1
+        let dividend = vec![
+            Scalar::from(5),
+            Scalar::from(13),
+            Scalar::from(22),
+            Scalar::from(15),
+            Scalar::ZERO,
+        ];
+        let divisor = vec![Scalar::from(4), Scalar::from(5)];
+
+        let (quotient, remainder) = poly_div_rem(&dividend, &divisor);
+        assert_eq!(
+            quotient,
+            vec![Scalar::from(1), Scalar::from(2), Scalar::from(3)]
+        );
+        assert_eq!(remainder, vec![Scalar::from(1)]);
+
+        // Exact division leaves no remainder
+        let (quotient, remainder) = poly_div_rem(&dividend[1..], &divisor[1..]);
+        assert_eq!(quotient.len(), 3);
+        assert!(remainder.is_empty());
+
+        // A dividend with a smaller degree is the remainder
+        let (quotient, remainder) = poly_div_rem(&divisor, &dividend);
+        assert!(quotient.is_empty());
+        assert_eq!(remainder, divisor);
+    }
+
+    #[test]
+    fn large_polynomial_multiplication_and_division() {
+        let mut rng = rand::thread_rng();
+        let mut random_poly =
+            |len: usize| -> PolyCoeff { (0..len).map(|_| Scalar::random(&mut rng)).collect() };
+
+        for (dividend_len, divisor_len) in [(200, 64), (300, 100), (1000, 999), (1025, 500)] {
+            let dividend = random_poly(dividend_len);
+            let divisor = random_poly(divisor_len);
+
+            // Check the FFT product against the naive product
+            let product = poly_mul(dividend.clone(), divisor.clone());
+            let mut expected = vec![Scalar::ZERO; dividend_len + divisor_len - 1];
+            for (i, a) in dividend.iter().enumerate() {
+                for (j, b) in divisor.iter().enumerate() {
+                    expected[i + j] += a * b;
+                }
+            }
+            assert_eq!(product, expected);
+
+            // The quotient and remainder are unique, so it is enough to check that
+            // f(x) = q(x) * g(x) + r(x) and that r(x) has a smaller degree than g(x)
+            let (quotient, remainder) = poly_div_rem(&dividend, &divisor);
+            assert_eq!(quotient.len(), dividend_len - divisor_len + 1);
+            assert!(remainder.len() < divisor_len);
+            assert_eq!(
+                poly_add(poly_mul(quotient, divisor.clone()), remainder),
+                dividend
+            );
+
+            // Exact division leaves no remainder
+            let (quotient, remainder) = poly_div_rem(&product, &divisor);
+            assert_eq!(quotient, dividend);
+            assert!(remainder.is_empty());
+        }
+    }
+
     #[test]
     fn vanishing_polynomial_smoke_test() {
         // f(x) = (x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6
@@ -247,6 +532,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn vanishing_polynomial_subproduct_tree_matches_naive() {
+        for num_roots in [0, 1, 31, 32, 33, 100, 257] {
+            let roots: Vec<_> = (0..num_roots)
+                .map(|i| Scalar::from(i as u64 * 7 + 3))
+                .collect();
+            assert_eq!(
+                vanishing_poly_subproduct_tree(&roots),
+                vanishing_poly(&roots),
+                "num_roots = {}",
+                num_roots
+            );
+        }
+    }
+
     #[test]
     fn polynomial_interpolation_smoke_test() {
         // f(x) = 1 + 2x + 3x^2
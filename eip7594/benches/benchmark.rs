@@ -1,8 +1,9 @@
 use bls12_381::Scalar;
 use criterion::{criterion_group, criterion_main, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
 use rust_eth_kzg::{
     constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB},
-    Bytes48Ref, Cell, CellIndex, CellRef, DASContext, KZGCommitment, KZGProof, RowIndex,
+    Bytes48Ref, Cell, CellIndex, CellRef, DASContext, KZGCommitment, KZGProof, PrecomputationLevel,
     TrustedSetup,
 };
 
@@ -19,10 +20,7 @@ fn dummy_blob() -> [u8; BYTES_PER_BLOB] {
     blob.try_into().unwrap()
 }
 
-fn dummy_commitment_cells_and_proofs() -> (
-    KZGCommitment,
-    ([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]),
-) {
+fn dummy_commitment_cells_and_proofs() -> (KZGCommitment, (Vec<Cell>, Vec<KZGProof>)) {
     let ctx = DASContext::default();
     let blob = dummy_blob();
 
@@ -38,7 +36,7 @@ pub fn bench_compute_cells_and_kzg_proofs(c: &mut Criterion) {
     let blob = dummy_blob();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(&trusted_setup, num_threads);
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
         c.bench_function(
             &format!(
                 "computing cells_and_kzg_proofs - NUM_THREADS: {}",
@@ -49,6 +47,56 @@ pub fn bench_compute_cells_and_kzg_proofs(c: &mut Criterion) {
     }
 }
 
+pub fn bench_compute_cells_and_kzg_proofs_batch(c: &mut Criterion) {
+    const NUM_BLOBS: usize = 16;
+    let trusted_setup = TrustedSetup::default();
+
+    let blob = dummy_blob();
+    let blobs = vec![&blob; NUM_BLOBS];
+
+    for num_threads in THREAD_COUNTS {
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
+        c.bench_function(
+            &format!(
+                "computing cells_and_kzg_proofs for {} blobs - NUM_THREADS: {}",
+                NUM_BLOBS, num_threads
+            ),
+            |b| b.iter(|| ctx.compute_cells_and_kzg_proofs_batch(&blobs)),
+        );
+    }
+}
+
+// The precomputation levels that we benchmark the prover with.
+const PRECOMPUTATION_LEVELS: [PrecomputationLevel; 5] = [
+    PrecomputationLevel::None,
+    PrecomputationLevel::WindowBits(4),
+    PrecomputationLevel::WindowBits(6),
+    PrecomputationLevel::WindowBits(8),
+    PrecomputationLevel::WindowBits(10),
+];
+
+pub fn bench_compute_cells_and_kzg_proofs_precomputation(c: &mut Criterion) {
+    const NUM_THREADS: usize = 1;
+    let trusted_setup = TrustedSetup::default();
+
+    let blob = dummy_blob();
+
+    for precomputation in PRECOMPUTATION_LEVELS {
+        let ctx = DASContext::new(&trusted_setup, NUM_THREADS, precomputation).unwrap();
+        // The serialized precomputation stores the same points as the precomputation held in memory,
+        // so we use its size to report the memory used.
+        let precomputation_size = ctx.prover_ctx().unwrap().precomputation_to_bytes().len();
+        c.bench_function(
+            &format!(
+                "computing cells_and_kzg_proofs - PRECOMPUTATION: {:?} ({} MiB)",
+                precomputation,
+                precomputation_size / (1024 * 1024)
+            ),
+            |b| b.iter(|| ctx.compute_cells_and_kzg_proofs(&blob)),
+        );
+    }
+}
+
 pub fn bench_recover_cells_and_compute_kzg_proofs(c: &mut Criterion) {
     let trusted_setup = TrustedSetup::default();
 
@@ -64,7 +112,7 @@ pub fn bench_recover_cells_and_compute_kzg_proofs(c: &mut Criterion) {
         .collect::<Vec<_>>();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(&trusted_setup, num_threads);
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
         c.bench_function(
             &format!(
                 "worse-case recover_cells_and_compute_proofs - NUM_THREADS: {}",
@@ -90,7 +138,7 @@ pub fn bench_verify_cell_kzg_proof_batch(c: &mut Criterion) {
     let proof_refs: Vec<Bytes48Ref> = proofs.iter().map(|proof| proof).collect();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(&trusted_setup, num_threads);
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
         c.bench_function(
             &format!("verify_cell_kzg_proof_batch - NUM_THREADS: {}", num_threads),
             |b| {
@@ -107,21 +155,90 @@ pub fn bench_verify_cell_kzg_proof_batch(c: &mut Criterion) {
     }
 }
 
+pub fn bench_verify_cell_kzg_proof_batch_with_rng(c: &mut Criterion) {
+    let trusted_setup = TrustedSetup::default();
+
+    let (commitment, (cells, proofs)) = dummy_commitment_cells_and_proofs();
+
+    let commitments = vec![&commitment; CELLS_PER_EXT_BLOB];
+    let cell_indices: Vec<CellIndex> = (0..CELLS_PER_EXT_BLOB).map(|x| x as CellIndex).collect();
+    let cell_refs: Vec<CellRef> = cells.iter().map(|cell| cell.as_ref()).collect();
+    let proof_refs: Vec<Bytes48Ref> = proofs.iter().collect();
+
+    let mut rng = StdRng::from_entropy();
+    for num_threads in THREAD_COUNTS {
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
+        c.bench_function(
+            &format!(
+                "verify_cell_kzg_proof_batch_with_rng - NUM_THREADS: {}",
+                num_threads
+            ),
+            |b| {
+                b.iter(|| {
+                    ctx.verify_cell_kzg_proof_batch_with_rng(
+                        &mut rng,
+                        commitments.clone(),
+                        cell_indices.clone(),
+                        cell_refs.clone(),
+                        proof_refs.clone(),
+                    )
+                })
+            },
+        );
+    }
+}
+
+pub fn bench_verify_data_column(c: &mut Criterion) {
+    const NUM_BLOBS: usize = 16;
+    const COLUMN_INDEX: CellIndex = 0;
+    let trusted_setup = TrustedSetup::default();
+
+    let (commitment, (cells, proofs)) = dummy_commitment_cells_and_proofs();
+
+    let commitments = vec![&commitment; NUM_BLOBS];
+    let cell_refs: Vec<CellRef> = vec![cells[COLUMN_INDEX as usize].as_ref(); NUM_BLOBS];
+    let proof_refs: Vec<Bytes48Ref> = vec![&proofs[COLUMN_INDEX as usize]; NUM_BLOBS];
+
+    for num_threads in THREAD_COUNTS {
+        let ctx = DASContext::with_threads(&trusted_setup, num_threads).unwrap();
+        c.bench_function(
+            &format!(
+                "verify_data_column for {} blobs - NUM_THREADS: {}",
+                NUM_BLOBS, num_threads
+            ),
+            |b| {
+                b.iter(|| {
+                    ctx.verify_data_column(
+                        COLUMN_INDEX,
+                        commitments.clone(),
+                        cell_refs.clone(),
+                        proof_refs.clone(),
+                    )
+                })
+            },
+        );
+    }
+}
+
 pub fn bench_init_context(c: &mut Criterion) {
     const NUM_THREADS: usize = 1;
     c.bench_function(&format!("Initialize context"), |b| {
         b.iter(|| {
             let trusted_setup = TrustedSetup::default();
-            DASContext::with_threads(&trusted_setup, NUM_THREADS)
+            DASContext::with_threads(&trusted_setup, NUM_THREADS).unwrap()
         })
     });
 }
 
 criterion_group!(
     benches,
-    bench_init_context
+    bench_init_context,
     bench_compute_cells_and_kzg_proofs,
+    bench_compute_cells_and_kzg_proofs_batch,
+    bench_compute_cells_and_kzg_proofs_precomputation,
     bench_recover_cells_and_compute_kzg_proofs,
-    bench_verify_cell_kzg_proof_batch
+    bench_verify_cell_kzg_proof_batch,
+    bench_verify_cell_kzg_proof_batch_with_rng,
+    bench_verify_data_column
 );
 criterion_main!(benches);
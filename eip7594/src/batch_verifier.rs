@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use bls12_381::G1Point;
+use kzg_multi_open::MultiOpeningAccumulator;
+use rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
+
+use crate::{
+    errors::{Error, VerifierError},
+    serialization::{deserialize_cell_to_scalars, deserialize_compressed_g1},
+    verifier::validation,
+    Bytes48Ref, CellIndex, CellRef, DASContext, KZGCommitment,
+};
+
+/// Accumulates cells, so that they can be verified together with a single pairing check.
+///
+/// This is useful when cells arrive one at a time, since each cell is folded into a
+/// running sum when it is pushed and does not need to be kept around until the batch
+/// is verified.
+///
+/// Unlike `verify_cell_kzg_proof_batch`, the cells are batched using independent random
+/// values rather than Fiat-Shamir, since the batch is not known up front.
+/// The random values are sampled from an RNG that is owned by the batch.
+#[derive(Debug)]
+pub struct CellBatchVerifier<'a> {
+    ctx: &'a DASContext,
+    /// The commitments that have been pushed so far, so that each commitment is only
+    /// deserialized once.
+    commitments: HashMap<KZGCommitment, G1Point>,
+    accumulator: MultiOpeningAccumulator,
+    /// The RNG used to sample the random value for each cell.
+    rng: StdRng,
+}
+
+impl DASContext {
+    /// Creates a `CellBatchVerifier` that verifies cells using this context.
+    ///
+    /// The random values are sampled from an RNG that is seeded using the operating system.
+    pub fn cell_batch_verifier(&self) -> CellBatchVerifier<'_> {
+        self.cell_batch_verifier_with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates a `CellBatchVerifier` that verifies cells using this context, where the random
+    /// values are sampled from an RNG that is seeded from `rng`.
+    pub fn cell_batch_verifier_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        mut rng: R,
+    ) -> CellBatchVerifier<'_> {
+        let mut seed = <StdRng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+
+        CellBatchVerifier {
+            ctx: self,
+            commitments: HashMap::new(),
+            accumulator: MultiOpeningAccumulator::new(),
+            rng: StdRng::from_seed(seed),
+        }
+    }
+}
+
+impl CellBatchVerifier<'_> {
+    /// Returns the number of cells that have been pushed.
+    pub fn len(&self) -> usize {
+        self.accumulator.len()
+    }
+
+    /// Returns true if no cells have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.accumulator.is_empty()
+    }
+
+    /// Adds a cell, along with its proof and the commitment to its blob, to the batch.
+    ///
+    /// Returns an error if any of the inputs are malformed. In that case, the batch is
+    /// left unchanged.
+    pub fn push(
+        &mut self,
+        commitment: Bytes48Ref,
+        cell_index: CellIndex,
+        cell: CellRef,
+        proof_bytes: Bytes48Ref,
+    ) -> Result<(), Error> {
+        // Validation
+        //
+        validation::cell_batch_verifier_push(self.ctx.config(), cell_index, cell)?;
+
+        // Deserialization
+        //
+        let commitment_ = match self.commitments.get(commitment) {
+            Some(commitment_) => *commitment_,
+            None => deserialize_compressed_g1(commitment)?,
+        };
+        let proof_ = deserialize_compressed_g1(proof_bytes)?;
+        let coset_evals = deserialize_cell_to_scalars(cell)?;
+
+        // Computation
+        //
+        self.commitments.insert(*commitment, commitment_);
+        self.accumulator.push(
+            &mut self.rng,
+            &self.ctx.verifier_ctx.kzg_multipoint_verifier,
+            commitment_,
+            cell_index,
+            &coset_evals,
+            proof_,
+        );
+
+        Ok(())
+    }
+
+    /// Adds the cells from `other` to this batch.
+    ///
+    /// This allows cells to be pushed on different threads and verified together.
+    ///
+    /// Returns an error if the batches were created from contexts with a different config or
+    /// a different trusted setup. In that case, this batch is left unchanged.
+    pub fn merge(&mut self, other: CellBatchVerifier) -> Result<(), Error> {
+        let (verifier_ctx, other_verifier_ctx) = (&self.ctx.verifier_ctx, &other.ctx.verifier_ctx);
+        if verifier_ctx.config != other_verifier_ctx.config
+            || verifier_ctx.trusted_setup_digest != other_verifier_ctx.trusted_setup_digest
+        {
+            return Err(VerifierError::CannotMergeBatchesFromDifferentContexts.into());
+        }
+
+        self.commitments.extend(other.commitments);
+        self.accumulator.merge(other.accumulator);
+
+        Ok(())
+    }
+
+    /// Verifies all of the cells that have been pushed.
+    ///
+    /// An empty batch is valid.
+    pub fn finalize(self) -> Result<(), Error> {
+        self.ctx.thread_pool.install(|| {
+            let ok = self
+                .accumulator
+                .finalize(&self.ctx.verifier_ctx.kzg_multipoint_verifier);
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        constants::CELLS_PER_EXT_BLOB,
+        errors::{Error, VerifierError},
+        test_utils::dummy_blobs,
+        DASConfig, DASContext, DASContextBuilder, TrustedSetup,
+    };
+
+    #[test]
+    fn cell_batch_verifier_accumulates_and_merges_cells() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let mut rows = Vec::new();
+        for blob in &blobs {
+            let blob = blob.as_slice().try_into().unwrap();
+            let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+            let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+            rows.push((commitment, cells, proofs));
+        }
+
+        // Push every 5th cell of each blob, alternating between two batches
+        // that are filled on different threads.
+        let (mut batch, other_batch) = std::thread::scope(|s| {
+            let batches: Vec<_> = (0..2)
+                .map(|thread| {
+                    let ctx = &ctx;
+                    let rows = &rows;
+                    s.spawn(move || {
+                        let mut batch =
+                            ctx.cell_batch_verifier_with_rng(StdRng::seed_from_u64(thread as u64));
+                        for (commitment, cells, proofs) in rows {
+                            for cell_index in (thread * 5..CELLS_PER_EXT_BLOB).step_by(10) {
+                                batch
+                                    .push(
+                                        commitment,
+                                        cell_index as u64,
+                                        &cells[cell_index],
+                                        &proofs[cell_index],
+                                    )
+                                    .unwrap();
+                            }
+                        }
+                        batch
+                    })
+                })
+                .collect();
+            let mut batches = batches.into_iter().map(|handle| handle.join().unwrap());
+            (batches.next().unwrap(), batches.next().unwrap())
+        });
+        batch.merge(other_batch).unwrap();
+        let num_cells_per_blob = (0..CELLS_PER_EXT_BLOB).step_by(5).count();
+        assert_eq!(batch.len(), rows.len() * num_cells_per_blob);
+        batch.finalize().unwrap();
+
+        assert!(ctx.cell_batch_verifier().finalize().is_ok());
+
+        // A cell checked against the wrong commitment is rejected
+        let (commitment, cells, proofs) = &rows[0];
+        let mut batch = ctx.cell_batch_verifier();
+        batch.push(commitment, 64, &cells[64], &proofs[64]).unwrap();
+        batch.push(&rows[1].0, 65, &cells[65], &proofs[65]).unwrap();
+        assert!(batch.finalize().unwrap_err().invalid_proof());
+
+        // Malformed cells are rejected when they are pushed
+        let mut batch = ctx.cell_batch_verifier();
+        assert!(matches!(
+            batch.push(commitment, CELLS_PER_EXT_BLOB as u64, &cells[0], &proofs[0]),
+            Err(Error::Verifier(VerifierError::CellIndexOutOfRange { .. }))
+        ));
+        assert!(matches!(
+            batch.push(commitment, 0, &cells[0][1..], &proofs[0]),
+            Err(Error::Verifier(
+                VerifierError::CellDoesNotContainEnoughBytes { .. }
+            ))
+        ));
+        assert!(batch.is_empty());
+
+        // Batches from contexts with a different config or trusted setup cannot be merged
+        let other_config_ctx = DASContextBuilder::new()
+            .config(DASConfig::new(32, 2).unwrap())
+            .verifier_only()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            batch.merge(other_config_ctx.cell_batch_verifier()),
+            Err(Error::Verifier(
+                VerifierError::CannotMergeBatchesFromDifferentContexts
+            ))
+        ));
+
+        let mut other_trusted_setup = TrustedSetup::default();
+        other_trusted_setup.g1_monomial.swap(0, 1);
+        let other_trusted_setup_ctx = DASContextBuilder::new()
+            .trusted_setup(&other_trusted_setup)
+            .verifier_only()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            batch.merge(other_trusted_setup_ctx.cell_batch_verifier()),
+            Err(Error::Verifier(
+                VerifierError::CannotMergeBatchesFromDifferentContexts
+            ))
+        ));
+    }
+}
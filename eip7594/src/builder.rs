@@ -0,0 +1,420 @@
+use std::{path::PathBuf, sync::Arc};
+
+use kzg_multi_open::PrecomputationLevel;
+use rayon::ThreadPool;
+
+use crate::{
+    errors::BuilderError,
+    prover::validate_precomputation_level,
+    thread_pool::ContextThreadPool,
+    trusted_setup::{load_embedded, TrustedSetupRef},
+    DASConfig, DASContext, ProverContext, TrustedSetup, VerifierContext,
+};
+
+/// The default number of threads used by a `DASContext`.
+const DEFAULT_NUM_THREADS: usize = 1;
+
+/// Where the trusted setup is loaded from.
+#[derive(Debug)]
+enum TrustedSetupSource<'a> {
+    /// The trusted setup that is embedded in the library.
+    Embedded,
+    /// A trusted setup that was already loaded by the caller.
+    Loaded(TrustedSetupRef<'a>),
+    /// A file containing the trusted setup in Json format.
+    JsonFile(PathBuf),
+}
+
+/// The thread pool that the context runs its methods on.
+#[derive(Debug)]
+enum ThreadPoolSource {
+    /// A new thread pool with the given number of threads is created.
+    NumThreads(usize),
+    /// A thread pool that is managed by the caller.
+    External(Arc<ThreadPool>),
+    /// The thread pool of the caller, or the global thread pool.
+    Ambient,
+}
+
+/// A builder used to configure how a `DASContext` is created.
+///
+/// By default, the context uses the embedded trusted setup, the mainnet config, a single
+/// thread and creates both the prover and the verifier.
+#[derive(Debug)]
+pub struct DASContextBuilder<'a> {
+    trusted_setup: TrustedSetupSource<'a>,
+    config: DASConfig,
+    thread_pool: ThreadPoolSource,
+    with_prover: bool,
+    precomputation: PrecomputationLevel,
+    precomputation_file: Option<PathBuf>,
+}
+
+impl Default for DASContextBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DASContextBuilder<'a> {
+    pub fn new() -> Self {
+        DASContextBuilder {
+            trusted_setup: TrustedSetupSource::Embedded,
+            config: DASConfig::default(),
+            thread_pool: ThreadPoolSource::NumThreads(DEFAULT_NUM_THREADS),
+            with_prover: true,
+            precomputation: PrecomputationLevel::default(),
+            precomputation_file: None,
+        }
+    }
+
+    /// Uses a trusted setup that was already loaded.
+    ///
+    /// This can either be a `TrustedSetup` or a `DecodedTrustedSetup`.
+    pub fn trusted_setup(mut self, trusted_setup: impl Into<TrustedSetupRef<'a>>) -> Self {
+        self.trusted_setup = TrustedSetupSource::Loaded(trusted_setup.into());
+        self
+    }
+
+    /// Loads the trusted setup from a Json file when the context is built.
+    pub fn trusted_setup_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.trusted_setup = TrustedSetupSource::JsonFile(path.into());
+        self
+    }
+
+    /// Sets the cell size and extension factor that the context uses.
+    ///
+    /// The config is checked against the trusted setup when the context is built.
+    pub fn config(mut self, config: DASConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Creates a new thread pool with `num_threads` threads for the context.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.thread_pool = ThreadPoolSource::NumThreads(num_threads);
+        self
+    }
+
+    /// Runs the methods of the context on a thread pool that is managed by the caller.
+    ///
+    /// The same thread pool can be shared between multiple contexts.
+    pub fn thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = ThreadPoolSource::External(thread_pool);
+        self
+    }
+
+    /// Runs the methods of the context on the thread pool of the caller, instead of
+    /// a thread pool owned by the context.
+    ///
+    /// If the methods are called from outside of a rayon thread pool, the global rayon
+    /// thread pool is used.
+    pub fn ambient_thread_pool(mut self) -> Self {
+        self.thread_pool = ThreadPoolSource::Ambient;
+        self
+    }
+
+    /// Sets whether the prover should be created.
+    ///
+    /// Creating the prover is expensive, so contexts that only verify proofs and
+    /// recover cells should not create it.
+    pub fn with_prover(mut self, with_prover: bool) -> Self {
+        self.with_prover = with_prover;
+        self
+    }
+
+    /// Builds a context without the prover.
+    ///
+    /// This is the same as calling `with_prover(false)`.
+    pub fn verifier_only(self) -> Self {
+        self.with_prover(false)
+    }
+
+    /// Sets the amount of precomputation that the prover does.
+    ///
+    /// The window size is checked when the context is built.
+    /// This is ignored if the precomputation is loaded from a file.
+    pub fn precomputation_level(mut self, precomputation: PrecomputationLevel) -> Self {
+        self.precomputation = precomputation;
+        self
+    }
+
+    /// Loads the prover precomputation from a file that was created using
+    /// `ProverContext::precomputation_to_bytes`, instead of computing it.
+    pub fn precomputation_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.precomputation_file = Some(path.into());
+        self
+    }
+
+    /// Builds the `DASContext`.
+    pub fn build(self) -> Result<DASContext, BuilderError> {
+        // Check the precomputation level before doing any expensive work
+        if self.with_prover && self.precomputation_file.is_none() {
+            validate_precomputation_level(self.precomputation)?;
+        }
+
+        let embedded_trusted_setup;
+        let loaded_trusted_setup;
+        let trusted_setup = match self.trusted_setup {
+            TrustedSetupSource::Embedded => {
+                embedded_trusted_setup = load_embedded();
+                TrustedSetupRef::from(&embedded_trusted_setup)
+            }
+            TrustedSetupSource::Loaded(trusted_setup) => trusted_setup,
+            TrustedSetupSource::JsonFile(path) => {
+                loaded_trusted_setup = TrustedSetup::try_from_file(path)?;
+                TrustedSetupRef::from(&loaded_trusted_setup)
+            }
+        };
+
+        let thread_pool = match self.thread_pool {
+            ThreadPoolSource::NumThreads(num_threads) => ContextThreadPool::Explicit(Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(num_threads)
+                    .build()?,
+            )),
+            ThreadPoolSource::External(thread_pool) => ContextThreadPool::Explicit(thread_pool),
+            ThreadPoolSource::Ambient => ContextThreadPool::Ambient,
+        };
+
+        let verifier_ctx = VerifierContext::new(trusted_setup, self.config)?;
+
+        let prover_ctx = match (self.with_prover, self.precomputation_file) {
+            (false, _) => None,
+            (true, Some(path)) => Some(ProverContext::from_precomputation_file(
+                trusted_setup,
+                self.config,
+                path,
+            )?),
+            (true, None) => Some(ProverContext::new(
+                trusted_setup,
+                self.config,
+                self.precomputation,
+            )?),
+        };
+
+        Ok(DASContext {
+            thread_pool,
+            prover_ctx,
+            verifier_ctx,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::CELLS_PER_EXT_BLOB,
+        errors::{Error, ProverError, VerifierError},
+        test_utils::dummy_blob,
+        ConfigError, PrecomputationError, TrustedSetupError,
+    };
+
+    #[test]
+    fn verifier_only_context_rejects_prover_methods() {
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+
+        let ctx = DASContext::default();
+        let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+        let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        let verifier_ctx = DASContextBuilder::new().verifier_only().build().unwrap();
+        assert!(verifier_ctx.prover_ctx().is_none());
+
+        assert!(matches!(
+            verifier_ctx.blob_to_kzg_commitment(blob),
+            Err(Error::Prover(ProverError::ProverNotEnabled))
+        ));
+        assert!(matches!(
+            verifier_ctx.compute_cells_and_kzg_proofs(blob),
+            Err(Error::Prover(ProverError::ProverNotEnabled))
+        ));
+
+        // Computing cells, verification and recovering cells do not need the prover
+        assert_eq!(verifier_ctx.compute_cells(blob).unwrap(), cells);
+
+        let cell_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as u64).collect();
+        let cell_refs: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
+        verifier_ctx
+            .verify_cell_kzg_proof_batch(
+                vec![&commitment; CELLS_PER_EXT_BLOB],
+                cell_indices.clone(),
+                cell_refs.clone(),
+                proofs.iter().collect(),
+            )
+            .unwrap();
+
+        let half = CELLS_PER_EXT_BLOB / 2;
+        let recovered_cells = verifier_ctx
+            .recover_cells(cell_indices[half..].to_vec(), cell_refs[half..].to_vec())
+            .unwrap();
+        assert_eq!(recovered_cells, cells);
+    }
+
+    #[test]
+    fn builder_options_do_not_change_results() {
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+
+        let trusted_setup = TrustedSetup::default();
+        let expected = DASContext::default()
+            .compute_cells_and_kzg_proofs(blob)
+            .unwrap();
+
+        let thread_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let ctx = DASContextBuilder::new()
+            .trusted_setup(&trusted_setup)
+            .thread_pool(thread_pool.clone())
+            .precomputation_level(PrecomputationLevel::None)
+            .build()
+            .unwrap();
+        assert_eq!(ctx.compute_cells_and_kzg_proofs(blob).unwrap(), expected);
+
+        // A second context can share the same thread pool
+        let other_ctx = DASContextBuilder::new()
+            .trusted_setup(&trusted_setup)
+            .thread_pool(thread_pool.clone())
+            .build()
+            .unwrap();
+        assert_eq!(
+            other_ctx.compute_cells_and_kzg_proofs(blob).unwrap(),
+            expected
+        );
+
+        // The context can also run on the thread pool of the caller
+        let ambient_ctx = DASContextBuilder::new()
+            .trusted_setup(&trusted_setup)
+            .ambient_thread_pool()
+            .build()
+            .unwrap();
+        assert_eq!(
+            ambient_ctx.compute_cells_and_kzg_proofs(blob).unwrap(),
+            expected
+        );
+        assert_eq!(
+            thread_pool.install(|| ambient_ctx.compute_cells_and_kzg_proofs(blob).unwrap()),
+            expected
+        );
+    }
+
+    #[test]
+    fn non_mainnet_configs_round_trip() {
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+
+        for (field_elements_per_cell, extension_factor) in [(32, 2), (64, 4)] {
+            let config = DASConfig::new(field_elements_per_cell, extension_factor).unwrap();
+            let ctx = DASContextBuilder::new()
+                .config(config)
+                .precomputation_level(PrecomputationLevel::None)
+                .build()
+                .unwrap();
+            assert_eq!(ctx.config(), &config);
+
+            let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+            let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+            assert_eq!(cells.len(), config.cells_per_ext_blob());
+            assert!(cells
+                .iter()
+                .all(|cell| cell.len() == config.bytes_per_cell()));
+
+            let cell_indices: Vec<_> = (0..config.cells_per_ext_blob() as u64).collect();
+            let cell_refs: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
+            ctx.verify_cell_kzg_proof_batch(
+                vec![&commitment; cells.len()],
+                cell_indices.clone(),
+                cell_refs.clone(),
+                proofs.iter().collect(),
+            )
+            .unwrap();
+
+            // Recover using the minimum number of cells
+            let num_cells_needed = config.cells_per_ext_blob() / extension_factor;
+            let (recovered_cells, recovered_proofs) = ctx
+                .recover_cells_and_proofs(
+                    cell_indices[..num_cells_needed].to_vec(),
+                    cell_refs[..num_cells_needed].to_vec(),
+                )
+                .unwrap();
+            assert_eq!(recovered_cells, cells);
+            assert_eq!(recovered_proofs, proofs);
+
+            // Cells of a different size are rejected
+            let wrong_size_cells: Vec<_> = cells[..num_cells_needed]
+                .iter()
+                .map(|cell| [cell.as_ref(), &[0; 32]].concat())
+                .collect();
+            assert!(matches!(
+                ctx.recover_cells(
+                    cell_indices[..num_cells_needed].to_vec(),
+                    wrong_size_cells.iter().map(Vec::as_slice).collect(),
+                ),
+                Err(Error::Verifier(
+                    VerifierError::CellDoesNotContainEnoughBytes { .. }
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn builder_rejects_configs_unsupported_by_the_trusted_setup() {
+        // The embedded trusted setup only has 65 G2 points
+        let config = DASConfig::new(128, 2).unwrap();
+        assert!(matches!(
+            DASContextBuilder::new().config(config).build(),
+            Err(BuilderError::Config(ConfigError::NotEnoughG2Points { .. }))
+        ));
+    }
+
+    #[test]
+    fn builder_rejects_unsupported_window_sizes() {
+        for window_bits in [0, PrecomputationLevel::MAX_WINDOW_BITS + 1] {
+            assert!(matches!(
+                DASContextBuilder::new()
+                    .precomputation_level(PrecomputationLevel::WindowBits(window_bits))
+                    .build(),
+                Err(BuilderError::Config(
+                    ConfigError::InvalidPrecomputationWindowBits { window_bits: got, .. }
+                )) if got == window_bits
+            ));
+            assert!(matches!(
+                ProverContext::new(
+                    &TrustedSetup::default(),
+                    DASConfig::default(),
+                    PrecomputationLevel::WindowBits(window_bits)
+                ),
+                Err(ConfigError::InvalidPrecomputationWindowBits { .. })
+            ));
+        }
+
+        // The window size does not matter when the prover is not created
+        assert!(DASContextBuilder::new()
+            .verifier_only()
+            .precomputation_level(PrecomputationLevel::WindowBits(0))
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn builder_reports_missing_files() {
+        assert!(matches!(
+            DASContextBuilder::new()
+                .trusted_setup_file("does_not_exist.json")
+                .build(),
+            Err(BuilderError::TrustedSetup(TrustedSetupError::Io(_)))
+        ));
+        assert!(matches!(
+            DASContextBuilder::new()
+                .precomputation_file("does_not_exist.bin")
+                .build(),
+            Err(BuilderError::Precomputation(PrecomputationError::Io(_)))
+        ));
+    }
+}
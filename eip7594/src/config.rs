@@ -0,0 +1,183 @@
+use crate::{
+    constants::{
+        BYTES_PER_FIELD_ELEMENT, EXTENSION_FACTOR, FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL,
+    },
+    errors::ConfigError,
+    trusted_setup::TrustedSetupRef,
+};
+
+/// The parameters that describe how a blob is extended and split into cells.
+///
+/// The values in `constants` correspond to the mainnet preset, which is the default.
+/// Other presets can be used to evaluate different cell sizes and extension factors.
+///
+/// Note: The number of field elements in a blob is fixed by EIP-4844 and cannot be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DASConfig {
+    field_elements_per_cell: usize,
+    extension_factor: usize,
+}
+
+impl Default for DASConfig {
+    fn default() -> Self {
+        DASConfig::MAINNET
+    }
+}
+
+impl DASConfig {
+    /// The preset that is used on mainnet.
+    pub const MAINNET: DASConfig = DASConfig {
+        field_elements_per_cell: FIELD_ELEMENTS_PER_CELL,
+        extension_factor: EXTENSION_FACTOR,
+    };
+
+    /// Creates a new config with cells of `field_elements_per_cell` field elements,
+    /// where each blob is extended by `extension_factor`.
+    ///
+    /// Returns an error if the parameters do not describe a valid extension of a blob.
+    /// Whether the trusted setup is large enough for the config is checked when creating a context.
+    pub fn new(
+        field_elements_per_cell: usize,
+        extension_factor: usize,
+    ) -> Result<Self, ConfigError> {
+        if !field_elements_per_cell.is_power_of_two() {
+            return Err(ConfigError::CellSizeNotPowerOfTwo {
+                field_elements_per_cell,
+            });
+        }
+
+        // A proof cannot attest to all of the points in the blob, so we need at least two cells per blob.
+        let max_field_elements_per_cell = FIELD_ELEMENTS_PER_BLOB / 2;
+        if field_elements_per_cell > max_field_elements_per_cell {
+            return Err(ConfigError::CellSizeTooLarge {
+                field_elements_per_cell,
+                max_field_elements_per_cell,
+            });
+        }
+
+        // An extension factor of one would not add any redundancy.
+        if !extension_factor.is_power_of_two() || extension_factor < 2 {
+            return Err(ConfigError::InvalidExtensionFactor { extension_factor });
+        }
+
+        Ok(DASConfig {
+            field_elements_per_cell,
+            extension_factor,
+        })
+    }
+
+    /// The number of field elements in a cell.
+    pub fn field_elements_per_cell(&self) -> usize {
+        self.field_elements_per_cell
+    }
+
+    /// The factor by which a blob is extended.
+    pub fn extension_factor(&self) -> usize {
+        self.extension_factor
+    }
+
+    /// The number of field elements needed to represent a blob.
+    pub fn field_elements_per_blob(&self) -> usize {
+        FIELD_ELEMENTS_PER_BLOB
+    }
+
+    /// The number of field elements needed to represent an extended blob.
+    pub fn field_elements_per_ext_blob(&self) -> usize {
+        self.extension_factor * FIELD_ELEMENTS_PER_BLOB
+    }
+
+    /// The number of cells in an extended blob.
+    ///
+    /// Note: Each cell comes with its own proof.
+    pub fn cells_per_ext_blob(&self) -> usize {
+        self.field_elements_per_ext_blob() / self.field_elements_per_cell
+    }
+
+    /// The number of bytes needed to represent a cell.
+    pub fn bytes_per_cell(&self) -> usize {
+        self.field_elements_per_cell * BYTES_PER_FIELD_ELEMENT
+    }
+
+    /// Checks that the trusted setup contains enough points for this config.
+    ///
+    /// The verifier commits to the vanishing polynomial of a cell in G2, so the trusted setup
+    /// needs one more G2 point than the number of field elements in a cell.
+    pub fn validate<'a>(
+        &self,
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+    ) -> Result<(), ConfigError> {
+        let min_num_g2_points = self.field_elements_per_cell + 1;
+        let num_g2_points = trusted_setup.into().num_g2_points();
+        if num_g2_points < min_num_g2_points {
+            return Err(ConfigError::NotEnoughG2Points {
+                num_g2_points,
+                min_num_g2_points,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{BYTES_PER_CELL, CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_EXT_BLOB},
+        TrustedSetup,
+    };
+
+    #[test]
+    fn mainnet_config_matches_constants() {
+        let config = DASConfig::default();
+
+        assert_eq!(config, DASConfig::MAINNET);
+        assert_eq!(config.field_elements_per_cell(), FIELD_ELEMENTS_PER_CELL);
+        assert_eq!(config.extension_factor(), EXTENSION_FACTOR);
+        assert_eq!(
+            config.field_elements_per_ext_blob(),
+            FIELD_ELEMENTS_PER_EXT_BLOB
+        );
+        assert_eq!(config.cells_per_ext_blob(), CELLS_PER_EXT_BLOB);
+        assert_eq!(config.bytes_per_cell(), BYTES_PER_CELL);
+
+        assert!(config.validate(&TrustedSetup::default()).is_ok());
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        assert!(matches!(
+            DASConfig::new(48, 2),
+            Err(ConfigError::CellSizeNotPowerOfTwo { .. })
+        ));
+        assert!(matches!(
+            DASConfig::new(FIELD_ELEMENTS_PER_BLOB, 2),
+            Err(ConfigError::CellSizeTooLarge { .. })
+        ));
+        assert!(matches!(
+            DASConfig::new(64, 1),
+            Err(ConfigError::InvalidExtensionFactor { .. })
+        ));
+        assert!(matches!(
+            DASConfig::new(64, 3),
+            Err(ConfigError::InvalidExtensionFactor { .. })
+        ));
+    }
+
+    #[test]
+    fn cell_size_is_bounded_by_the_trusted_setup() {
+        let trusted_setup = TrustedSetup::default();
+
+        let config = DASConfig::new(128, 2).unwrap();
+        assert!(matches!(
+            config.validate(&trusted_setup),
+            Err(ConfigError::NotEnoughG2Points {
+                num_g2_points: 65,
+                min_num_g2_points: 129
+            })
+        ));
+
+        let config = DASConfig::new(32, 4).unwrap();
+        assert!(config.validate(&trusted_setup).is_ok());
+    }
+}
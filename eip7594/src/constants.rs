@@ -1,5 +1,8 @@
 // Note: Any mention of field elements in this file and in general in the codebase
 // refers to the scalar field.
+//
+// Note: The values that depend on the cell size or the extension factor correspond to the
+// mainnet preset. Other presets can be used by creating a context with a `DASConfig`.
 
 /// The number of bytes needed to represent a field element.
 ///
@@ -8,8 +11,8 @@ pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
 
 /// The number of field elements in a cell.
 ///
-/// Note: This is user defined; modifying this value will change the number of proofs produced,
-/// the proof generation time and the time it takes to verify a proof.
+/// Note: This is user defined; using a different value via `DASConfig` will change the number of
+/// proofs produced, the proof generation time and the time it takes to verify a proof.
 ///
 /// Note: This value must be a power of two between 1 and 64. The greatest value is 64 because there
 /// are only 65 G2 points in the trusted setup. Technically, it's still feasible to have a cell with
@@ -29,8 +32,8 @@ pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEM
 
 /// The factor by which we extend a blob.
 ///
-/// Note: This is user defined; modifying this will change the number of proofs produced,
-/// proof generation time and the rate of the reed-solomon code.
+/// Note: This is user defined; using a different value via `DASConfig` will change the number of
+/// proofs produced, proof generation time and the rate of the reed-solomon code.
 pub const EXTENSION_FACTOR: usize = 2;
 
 /// The number of field elements needed to represent an extended blob.
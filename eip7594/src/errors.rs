@@ -1,6 +1,6 @@
 use erasure_codes::errors::RSError;
 
-use crate::CellIndex;
+use crate::{CellIndex, RowIndex};
 
 /// Errors that can occur either during proving or verification.
 #[derive(Debug)]
@@ -36,6 +36,14 @@ impl From<SerializationError> for Error {
 #[derive(Debug)]
 pub enum ProverError {
     RecoveryFailure(VerifierError),
+    /// The context was built without a prover, so it can only be used for verification
+    /// and recovering cells.
+    ProverNotEnabled,
+    /// The blobs given to `compute_extended_matrix` cannot be extended, since their number
+    /// is not a power of two.
+    NumBlobsNotPowerOfTwo {
+        num_blobs: usize,
+    },
 }
 
 impl From<VerifierError> for ProverError {
@@ -69,17 +77,52 @@ pub enum VerifierError {
         cell_index: CellIndex,
         max_number_of_cells: u64,
     },
+    /// The columns given to `recover_matrix` do not all contain the same number of cells.
+    ColumnsHaveDifferentLengths {
+        column_index: CellIndex,
+        num_cells: usize,
+        expected_num_cells: usize,
+    },
+    /// The number of rows given to `recover_extended_matrix` is not `COLUMN_EXTENSION_FACTOR`
+    /// times a power of two.
+    InvalidNumberOfRows {
+        num_rows: usize,
+    },
+    /// A row given to `recover_extended_matrix` does not have a position for each cell.
+    RowHasInvalidLength {
+        row_index: RowIndex,
+        num_cells: usize,
+        expected_num_cells: usize,
+    },
+    /// `recover_extended_matrix` could not recover any more cells, before all of the
+    /// cells were recovered.
+    NotEnoughCellsToRecoverMatrix {
+        num_missing_cells: usize,
+    },
     InvalidCommitmentIndex {
         commitment_index: u64,
         max_number_of_commitments: u64,
     },
     InvalidProof,
+    /// The batches given to `CellBatchVerifier::merge` were created from contexts with a
+    /// different config or a different trusted setup.
+    CannotMergeBatchesFromDifferentContexts,
     BatchVerificationInputsMustHaveSameLength {
         commitment_indices_len: usize,
         cell_indices_len: usize,
         cells_len: usize,
         proofs_len: usize,
     },
+    BlobBatchVerificationInputsMustHaveSameLength {
+        blobs_len: usize,
+        commitments_len: usize,
+        proofs_len: usize,
+    },
+    DataColumnInputsMustHaveSameLength {
+        commitments_len: usize,
+        cells_len: usize,
+        proofs_len: usize,
+    },
     ReedSolomon(RSError),
     PolynomialHasInvalidLength {
         num_coefficients: usize,
@@ -103,3 +146,134 @@ pub enum SerializationError {
     BlobHasInvalidLength { bytes: Vec<u8>, length: usize },
     G1PointHasInvalidLength { bytes: Vec<u8>, length: usize },
 }
+
+/// Errors that can occur while loading the trusted setup.
+#[derive(Debug)]
+pub enum TrustedSetupError {
+    /// The trusted setup file could not be read.
+    Io(std::io::Error),
+    /// The json string could not be parsed into a trusted setup.
+    MalformedJson { reason: String },
+    /// The txt string could not be parsed into a trusted setup.
+    MalformedTxt { reason: String },
+    /// The bytes could not be parsed into a binary trusted setup.
+    MalformedBytes { reason: String },
+    /// The binary trusted setup was encoded with a version that is not supported.
+    UnsupportedVersion { version: u32 },
+    /// The checksum of the binary trusted setup does not match its contents.
+    ChecksumMismatch,
+    /// One of the arrays of points is missing from the trusted setup.
+    MissingField { field: &'static str },
+    /// One of the arrays of points does not have the expected number of points.
+    UnexpectedNumberOfPoints {
+        field: &'static str,
+        num_points: usize,
+        expected_num_points: usize,
+    },
+    /// A point is not a `0x` prefixed hex string.
+    MalformedHex { field: &'static str, index: usize },
+    /// A point does not have the expected number of bytes.
+    PointHasInvalidLength {
+        field: &'static str,
+        index: usize,
+        num_bytes: usize,
+        expected_num_bytes: usize,
+    },
+    /// A point is not a valid encoding of a point on the curve.
+    PointNotOnCurve { field: &'static str, index: usize },
+    /// A point is on the curve, but not in the prime order subgroup.
+    PointNotInSubgroup { field: &'static str, index: usize },
+}
+
+impl From<std::io::Error> for TrustedSetupError {
+    fn from(value: std::io::Error) -> Self {
+        TrustedSetupError::Io(value)
+    }
+}
+
+/// Errors that can occur when creating a `DASConfig`, or when the trusted setup
+/// does not support it.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The number of field elements in a cell is not a power of two.
+    CellSizeNotPowerOfTwo { field_elements_per_cell: usize },
+    /// A cell contains too many field elements for a blob to be split into multiple cells.
+    CellSizeTooLarge {
+        field_elements_per_cell: usize,
+        max_field_elements_per_cell: usize,
+    },
+    /// The extension factor is not a power of two that is greater than one.
+    InvalidExtensionFactor { extension_factor: usize },
+    /// The trusted setup does not contain enough G2 points for the size of a cell.
+    NotEnoughG2Points {
+        num_g2_points: usize,
+        min_num_g2_points: usize,
+    },
+    /// The window size of the prover precomputation is zero or larger than the supported maximum.
+    InvalidPrecomputationWindowBits {
+        window_bits: usize,
+        max_window_bits: usize,
+    },
+}
+
+/// Errors that can occur while loading a serialized prover precomputation.
+#[derive(Debug)]
+pub enum PrecomputationError {
+    /// The precomputation file could not be read.
+    Io(std::io::Error),
+    /// The bytes could not be parsed into a prover precomputation.
+    MalformedBytes { reason: String },
+    /// The precomputation was encoded with a version that is not supported.
+    UnsupportedVersion { version: u32 },
+    /// The checksum of the precomputation does not match its contents.
+    ChecksumMismatch,
+    /// The precomputation was created from a different trusted setup.
+    TrustedSetupMismatch,
+    /// The config is not supported by the trusted setup.
+    Config(ConfigError),
+}
+
+impl From<std::io::Error> for PrecomputationError {
+    fn from(value: std::io::Error) -> Self {
+        PrecomputationError::Io(value)
+    }
+}
+impl From<ConfigError> for PrecomputationError {
+    fn from(value: ConfigError) -> Self {
+        PrecomputationError::Config(value)
+    }
+}
+
+/// Errors that can occur while building a `DASContext` using the `DASContextBuilder`.
+#[derive(Debug)]
+pub enum BuilderError {
+    /// The trusted setup could not be loaded.
+    TrustedSetup(TrustedSetupError),
+    /// The prover precomputation could not be loaded.
+    Precomputation(PrecomputationError),
+    /// The thread pool could not be created.
+    ThreadPool(rayon::ThreadPoolBuildError),
+    /// The config is not supported by the trusted setup.
+    Config(ConfigError),
+}
+
+impl From<TrustedSetupError> for BuilderError {
+    fn from(value: TrustedSetupError) -> Self {
+        BuilderError::TrustedSetup(value)
+    }
+}
+impl From<PrecomputationError> for BuilderError {
+    fn from(value: PrecomputationError) -> Self {
+        BuilderError::Precomputation(value)
+    }
+}
+impl From<rayon::ThreadPoolBuildError> for BuilderError {
+    fn from(value: rayon::ThreadPoolBuildError) -> Self {
+        BuilderError::ThreadPool(value)
+    }
+}
+impl From<ConfigError> for BuilderError {
+    fn from(value: ConfigError) -> Self {
+        BuilderError::Config(value)
+    }
+}
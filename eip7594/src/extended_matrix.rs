@@ -0,0 +1,406 @@
+use bls12_381::{g1_batch_normalize, G1Point, G1Projective, Scalar};
+use erasure_codes::ReedSolomon;
+use kzg_multi_open::{reverse_bit_order, ProverInput};
+use polynomial::domain::Domain;
+use rayon::prelude::*;
+
+use crate::{
+    errors::{Error, ProverError, VerifierError},
+    serialization::{
+        deserialize_blob_to_scalars, deserialize_cell_to_scalars, serialize_cells_and_proofs,
+        serialize_g1_compressed, serialize_scalars_to_cell,
+    },
+    verifier::validation,
+    BlobRef, Cell, CellIndex, CellRef, CellsAndProofs, DASContext, KZGCommitment,
+};
+
+/// The factor by which the columns of the blobs are extended.
+///
+/// Only the extension of the rows depends on `DASConfig::extension_factor`, since it determines
+/// the cells. The columns are always extended by a factor of two, as in the two-dimensional
+/// scheme, which keeps the number of rows small: every extra row needs its own commitment
+/// and proofs.
+pub(crate) const COLUMN_EXTENSION_FACTOR: usize = 2;
+
+/// The two-dimensional extension of a set of blobs.
+///
+/// Each row is the extended blob of a polynomial, as returned by `compute_cells_and_kzg_proofs`.
+/// The rows are also extended, so that every column is a Reed-Solomon codeword, which
+/// multiplies the number of rows by `COLUMN_EXTENSION_FACTOR`.
+///
+/// The columns are extended in the same bit-reversed order as the rows, so the first half of
+/// the rows are the rows of the blobs, in the order of the blobs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedMatrix {
+    /// The commitment to each row.
+    pub commitments: Vec<KZGCommitment>,
+    /// The cells and the proofs of each row.
+    pub rows: Vec<CellsAndProofs>,
+}
+
+impl DASContext {
+    /// Computes the two-dimensional extension of the given blobs.
+    ///
+    /// The number of blobs must be a power of two.
+    ///
+    /// The commitments to the extended rows are computed from the commitments to the blobs,
+    /// rather than by committing to the extended rows.
+    pub fn compute_extended_matrix(&self, blobs: &[BlobRef]) -> Result<ExtendedMatrix, Error> {
+        self.thread_pool.install(|| {
+            // Validation
+            //
+            if !blobs.len().is_power_of_two() {
+                return Err(ProverError::NumBlobsNotPowerOfTwo {
+                    num_blobs: blobs.len(),
+                }
+                .into());
+            }
+            let prover = &self.prover()?.kzg_multipoint_prover;
+
+            // Deserialization
+            //
+            let blobs = blobs
+                .par_iter()
+                .map(|blob| deserialize_blob_to_scalars(*blob))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Computation
+            //
+            // Commit to the blobs, and extend the commitments to the extended rows.
+            let mut commitments: Vec<G1Point> = blobs
+                .par_iter()
+                .map(|blob| prover.commit(ProverInput::Data(blob.clone())))
+                .collect();
+            let extended_commitments = extend_commitments(&commitments);
+            commitments.extend(extended_commitments);
+
+            // Extend each column of the blobs. The first half of each extended row is
+            // the data of its polynomial, in the same way as for the blobs.
+            let column_rs = ReedSolomon::new(blobs.len(), COLUMN_EXTENSION_FACTOR, 1);
+            let rows = extend_columns(&column_rs, &blobs);
+
+            let rows = rows
+                .into_par_iter()
+                .map(|data| {
+                    let (proofs, cells) =
+                        prover.compute_multi_opening_proofs(ProverInput::Data(data));
+                    serialize_cells_and_proofs(cells, proofs)
+                })
+                .collect();
+
+            Ok(ExtendedMatrix {
+                commitments: commitments.iter().map(serialize_g1_compressed).collect(),
+                rows,
+            })
+        })
+    }
+
+    /// Recovers all of the cells of a two-dimensional extension, given a subset of its cells.
+    ///
+    /// `rows[i][j]` is the cell in row `i` and column `j`, or `None` if it is missing. Every row
+    /// must have a position for each cell, and the number of rows must be `COLUMN_EXTENSION_FACTOR`
+    /// times a power of two.
+    ///
+    /// The missing cells are recovered by alternating between recovering each row and each
+    /// column that has at least half of its cells, until all of the cells have been recovered.
+    /// Returns an error if it gets stuck before then.
+    pub fn recover_extended_matrix(
+        &self,
+        rows: Vec<Vec<Option<CellRef>>>,
+    ) -> Result<Vec<Vec<Cell>>, Error> {
+        self.thread_pool.install(|| {
+            // Validation
+            //
+            validation::recover_extended_matrix(self.config(), &rows)?;
+
+            let mut rows: Vec<Vec<Option<Cell>>> = rows
+                .into_iter()
+                .map(|row| row.into_iter().map(|cell| cell.map(Cell::from)).collect())
+                .collect();
+            let column_rs = ReedSolomon::new(
+                rows.len() / COLUMN_EXTENSION_FACTOR,
+                COLUMN_EXTENSION_FACTOR,
+                1,
+            );
+
+            // Computation
+            //
+            let mut num_missing_cells = count_missing_cells(&rows);
+            while num_missing_cells > 0 {
+                rows.par_iter_mut()
+                    .try_for_each(|row| self.recover_extended_matrix_row(row))?;
+
+                let recovered_columns = (0..self.config().cells_per_ext_blob())
+                    .into_par_iter()
+                    .map(|column_index| {
+                        self.recover_extended_matrix_column(&column_rs, &rows, column_index)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                for (column_index, recovered_cells) in recovered_columns.into_iter().enumerate() {
+                    for (row_index, cell) in recovered_cells {
+                        rows[row_index][column_index] = Some(cell);
+                    }
+                }
+
+                let num_remaining_missing_cells = count_missing_cells(&rows);
+                if num_remaining_missing_cells == num_missing_cells {
+                    return Err(
+                        VerifierError::NotEnoughCellsToRecoverMatrix { num_missing_cells }.into(),
+                    );
+                }
+                num_missing_cells = num_remaining_missing_cells;
+            }
+
+            Ok(rows
+                .into_iter()
+                .map(|row| row.into_iter().flatten().collect())
+                .collect())
+        })
+    }
+
+    /// Fills in the missing cells of a row, if it has enough cells to be recovered.
+    fn recover_extended_matrix_row(&self, row: &mut [Option<Cell>]) -> Result<(), Error> {
+        let (cell_indices, cells): (Vec<_>, Vec<_>) = row
+            .iter()
+            .enumerate()
+            .filter_map(|(cell_index, cell)| Some((cell_index as CellIndex, cell.as_deref()?)))
+            .unzip();
+
+        let min_cells_needed = row.len() / self.config().extension_factor();
+        if cells.len() == row.len() || cells.len() < min_cells_needed {
+            return Ok(());
+        }
+
+        let recovered_cells = self.recover_cells(cell_indices, cells)?;
+        for (cell, recovered_cell) in row.iter_mut().zip(recovered_cells) {
+            cell.get_or_insert(recovered_cell);
+        }
+
+        Ok(())
+    }
+
+    /// Recovers the missing cells of a column, if it has enough cells to be recovered.
+    ///
+    /// Returns the recovered cells, along with the index of the row that each of them is in.
+    fn recover_extended_matrix_column(
+        &self,
+        column_rs: &ReedSolomon,
+        rows: &[Vec<Option<Cell>>],
+        column_index: usize,
+    ) -> Result<Vec<(usize, Cell)>, Error> {
+        let erasures: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row[column_index].is_none())
+            .map(|(row_index, _)| row_index)
+            .collect();
+        if erasures.is_empty() || erasures.len() > column_rs.acceptable_num_random_erasures() {
+            return Ok(Vec::new());
+        }
+
+        // Deserialize the column, filling in the missing cells with zeroes
+        let field_elements_per_cell = self.config().field_elements_per_cell();
+        let cells = rows
+            .iter()
+            .map(|row| match &row[column_index] {
+                Some(cell) => deserialize_cell_to_scalars(cell),
+                None => Ok(vec![Scalar::from(0u64); field_elements_per_cell]),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Each position in the cells is a separate codeword, with the same erasures
+        let vanishing_poly = column_rs
+            .systematic_erasure_vanishing_poly(erasures.clone())
+            .map_err(VerifierError::from)?;
+        let mut recovered_cells = vec![Vec::with_capacity(field_elements_per_cell); rows.len()];
+        for i in 0..field_elements_per_cell {
+            let partial_codeword = cells.iter().map(|cell| cell[i]).collect();
+            let data = column_rs
+                .decode_systematic_with_vanishing_poly(partial_codeword, &vanishing_poly)
+                .map_err(VerifierError::from)?;
+            let codeword = column_rs
+                .encode_systematic(data)
+                .expect("the decoded data has one value for every blob");
+
+            for (recovered_cell, value) in recovered_cells.iter_mut().zip(codeword) {
+                recovered_cell.push(value);
+            }
+        }
+
+        Ok(erasures
+            .into_iter()
+            .map(|row_index| {
+                let cell = serialize_scalars_to_cell(&recovered_cells[row_index]);
+                (row_index, cell.into_boxed_slice())
+            })
+            .collect())
+    }
+}
+
+/// Extends each column of the blobs, returning the data of every row of the extended matrix.
+///
+/// Since the blobs and the cells use the same bit-reversed ordering, the first half of each
+/// row is the data of the polynomial for that row.
+fn extend_columns(column_rs: &ReedSolomon, blobs: &[Vec<Scalar>]) -> Vec<Vec<Scalar>> {
+    let blob_len = blobs.first().map_or(0, Vec::len);
+
+    let extended_columns: Vec<Vec<Scalar>> = (0..blob_len)
+        .into_par_iter()
+        .map(|i| {
+            let column = blobs.iter().map(|blob| blob[i]).collect();
+            column_rs
+                .encode_systematic(column)
+                .expect("each column has one value for every blob")
+        })
+        .collect();
+
+    (0..column_rs.codeword_length())
+        .map(|row_index| {
+            extended_columns
+                .iter()
+                .map(|column| column[row_index])
+                .collect()
+        })
+        .collect()
+}
+
+/// Extends the commitments to the blobs, returning the commitments to the extended rows.
+///
+/// Committing to a polynomial is linear, so the commitments can be extended in the same way
+/// as the columns, using an FFT over the group instead of over the field.
+fn extend_commitments(commitments: &[G1Point]) -> Vec<G1Point> {
+    let num_blobs = commitments.len();
+
+    let mut commitments: Vec<G1Projective> = commitments.iter().map(G1Projective::from).collect();
+    reverse_bit_order(&mut commitments);
+    let commitments_coeff = Domain::new(num_blobs).ifft_g1(commitments);
+
+    let mut extended_commitments = Domain::new(2 * num_blobs).fft_g1(commitments_coeff);
+    reverse_bit_order(&mut extended_commitments);
+
+    g1_batch_normalize(&extended_commitments[num_blobs..])
+}
+
+/// Returns the number of cells that are missing from the matrix.
+fn count_missing_cells(rows: &[Vec<Option<Cell>>]) -> usize {
+    rows.iter().flatten().filter(|cell| cell.is_none()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::CELLS_PER_EXT_BLOB, test_utils::dummy_blobs, Bytes48Ref};
+
+    /// Keeps the cells in `rows[i]` that are at the column indices in `present[i]`.
+    fn partial_matrix<'a>(
+        matrix: &'a ExtendedMatrix,
+        present: &[std::ops::Range<usize>],
+    ) -> Vec<Vec<Option<CellRef<'a>>>> {
+        matrix
+            .rows
+            .iter()
+            .zip(present)
+            .map(|((cells, _), present)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(column_index, cell)| {
+                        present.contains(&column_index).then_some(cell.as_ref())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn extended_matrix_rows_are_committed_to_and_proven() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+
+        let matrix = ctx.compute_extended_matrix(&blob_refs).unwrap();
+        assert_eq!(matrix.commitments.len(), 2 * blobs.len());
+        assert_eq!(matrix.rows.len(), 2 * blobs.len());
+
+        // The first half of the matrix is the blobs
+        for (row_index, blob) in blob_refs.iter().enumerate() {
+            assert_eq!(
+                matrix.commitments[row_index],
+                ctx.blob_to_kzg_commitment(blob).unwrap()
+            );
+            assert_eq!(
+                matrix.rows[row_index],
+                ctx.compute_cells_and_kzg_proofs(blob).unwrap()
+            );
+        }
+
+        // The commitments to the extended rows were computed from the commitments to the
+        // blobs, so this checks that they match the cells of the extended rows
+        let mut commitments: Vec<Bytes48Ref> = Vec::new();
+        let mut cell_indices = Vec::new();
+        let mut cells: Vec<CellRef> = Vec::new();
+        let mut proofs: Vec<Bytes48Ref> = Vec::new();
+        for (commitment, (row_cells, row_proofs)) in matrix.commitments.iter().zip(&matrix.rows) {
+            for (cell_index, (cell, proof)) in row_cells.iter().zip(row_proofs).enumerate() {
+                commitments.push(commitment);
+                cell_indices.push(cell_index as CellIndex);
+                cells.push(cell);
+                proofs.push(proof);
+            }
+        }
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs)
+            .unwrap();
+
+        assert!(matches!(
+            ctx.compute_extended_matrix(&blob_refs[..0]),
+            Err(Error::Prover(ProverError::NumBlobsNotPowerOfTwo {
+                num_blobs: 0
+            }))
+        ));
+    }
+
+    #[test]
+    fn recover_extended_matrix_alternates_rows_and_columns() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+        let matrix = ctx.compute_extended_matrix(&blob_refs).unwrap();
+        let expected_rows: Vec<Vec<Cell>> =
+            matrix.rows.iter().map(|(cells, _)| cells.clone()).collect();
+
+        // None of the rows have enough cells to be recovered, until the columns that have
+        // half of their cells are recovered.
+        let present = [0..60, 0..60, 60..120, 60..120];
+        let recovered_rows = ctx
+            .recover_extended_matrix(partial_matrix(&matrix, &present))
+            .unwrap();
+        assert_eq!(recovered_rows, expected_rows);
+
+        // The columns can be recovered, but then none of the rows can be recovered
+        let present = [0..60, 0..60, 0..0, 0..0];
+        assert!(matches!(
+            ctx.recover_extended_matrix(partial_matrix(&matrix, &present)),
+            Err(Error::Verifier(
+                VerifierError::NotEnoughCellsToRecoverMatrix {
+                    num_missing_cells: 272
+                }
+            ))
+        ));
+
+        let present = [0..CELLS_PER_EXT_BLOB, 0..CELLS_PER_EXT_BLOB, 0..0];
+        assert!(matches!(
+            ctx.recover_extended_matrix(partial_matrix(&matrix, &present)),
+            Err(Error::Verifier(VerifierError::InvalidNumberOfRows {
+                num_rows: 3
+            }))
+        ));
+    }
+}
@@ -1,70 +1,109 @@
+mod batch_verifier;
+mod builder;
+mod config;
 pub mod constants;
 mod errors;
+mod extended_matrix;
+mod matrix;
 mod prover;
 mod serialization;
+#[cfg(test)]
+mod test_utils;
+mod thread_pool;
 mod trusted_setup;
 mod verifier;
 
 // Exported types
 //
-pub use errors::Error;
-pub use trusted_setup::TrustedSetup;
+pub use batch_verifier::CellBatchVerifier;
+pub use builder::DASContextBuilder;
+pub use config::DASConfig;
+pub use errors::{BuilderError, ConfigError, Error, PrecomputationError, TrustedSetupError};
+pub use extended_matrix::ExtendedMatrix;
+pub use kzg_multi_open::PrecomputationLevel;
+pub use matrix::{DataColumn, DataColumns, MatrixEntry};
+pub use prover::ProverContext;
+pub use trusted_setup::{DecodedTrustedSetup, TrustedSetup, TrustedSetupRef};
+pub use verifier::{ValidatedCommitment, VerifierContext};
 pub type BlobRef<'a> = &'a [u8; BYTES_PER_BLOB];
 pub type Bytes48Ref<'a> = &'a [u8; 48];
+pub type Bytes32Ref<'a> = &'a [u8; 32];
 
-// TODO: We require a bit of feedback re usage to know whether we should make
-// TODO: Cell type just be Vec<u8> -- This would avoid accidental stack overflows.
-pub type Cell = Box<[u8; BYTES_PER_CELL]>;
-pub type CellRef<'a> = &'a [u8; BYTES_PER_CELL];
+/// A cell contains `DASConfig::bytes_per_cell` bytes, which is `BYTES_PER_CELL` for the mainnet config.
+pub type Cell = Box<[u8]>;
+pub type CellRef<'a> = &'a [u8];
 
 pub type KZGProof = [u8; BYTES_PER_COMMITMENT];
+/// The cells of an extended blob, along with the KZG proof for each cell.
+pub type CellsAndProofs = (Vec<Cell>, Vec<KZGProof>);
 pub type KZGCommitment = [u8; BYTES_PER_COMMITMENT];
+/// A field element in its serialized big-endian form.
+///
+/// This is used for the evaluation returned when opening a polynomial at a point.
+pub type SerializedScalar = [u8; BYTES_PER_FIELD_ELEMENT];
 pub type CellIndex = u64;
 pub type RowIndex = u64;
 
-use constants::{BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_COMMITMENT};
-use prover::ProverContext;
-use rayon::ThreadPool;
-use std::sync::Arc;
-use verifier::VerifierContext;
+use constants::{BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT};
+use thread_pool::ContextThreadPool;
 
 /// The context that will be used to create and verify proofs.
 #[derive(Debug)]
 pub struct DASContext {
-    thread_pool: Arc<ThreadPool>,
-    pub prover_ctx: ProverContext,
+    thread_pool: ContextThreadPool,
+    /// This is `None` if the context was built without a prover.
+    pub prover_ctx: Option<ProverContext>,
     pub verifier_ctx: VerifierContext,
 }
 
 impl Default for DASContext {
     fn default() -> Self {
-        let trusted_setup = TrustedSetup::default();
-        const DEFAULT_NUM_THREADS: usize = 1;
-        DASContext::with_threads(&trusted_setup, DEFAULT_NUM_THREADS)
+        DASContextBuilder::new()
+            .build()
+            .expect("could not create the default context")
     }
 }
 
 impl DASContext {
-    pub fn with_threads(trusted_setup: &TrustedSetup, num_threads: usize) -> Self {
-        let thread_pool = std::sync::Arc::new(
-            rayon::ThreadPoolBuilder::new()
-                .num_threads(num_threads)
-                .build()
-                .unwrap(),
-        );
+    /// Creates a new context with its own thread pool of `num_threads` threads.
+    ///
+    /// Returns an error if the thread pool could not be created.
+    pub fn with_threads<'a>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        num_threads: usize,
+    ) -> Result<Self, BuilderError> {
+        DASContext::new(trusted_setup, num_threads, PrecomputationLevel::default())
+    }
 
-        DASContext {
-            thread_pool,
-            prover_ctx: ProverContext::new(trusted_setup),
-            verifier_ctx: VerifierContext::new(trusted_setup),
-        }
+    /// Creates a new context with its own thread pool of `num_threads` threads.
+    ///
+    /// `prover_precomputation` is the amount of precomputation that the prover does.
+    /// Verifier-heavy applications can lower it to save memory, at the cost of slower proving.
+    ///
+    /// Returns an error if the thread pool could not be created.
+    pub fn new<'a>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        num_threads: usize,
+        prover_precomputation: PrecomputationLevel,
+    ) -> Result<Self, BuilderError> {
+        DASContextBuilder::new()
+            .trusted_setup(trusted_setup)
+            .num_threads(num_threads)
+            .precomputation_level(prover_precomputation)
+            .build()
     }
 
-    pub fn prover_ctx(&self) -> &ProverContext {
-        &self.prover_ctx
+    /// Returns the prover context, or `None` if the context was built without a prover.
+    pub fn prover_ctx(&self) -> Option<&ProverContext> {
+        self.prover_ctx.as_ref()
     }
 
     pub fn verifier_ctx(&self) -> &VerifierContext {
         &self.verifier_ctx
     }
+
+    /// Returns the config that this context was created with.
+    pub fn config(&self) -> &DASConfig {
+        self.verifier_ctx.config()
+    }
 }
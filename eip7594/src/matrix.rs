@@ -0,0 +1,309 @@
+use rayon::prelude::*;
+
+use crate::{
+    errors::Error, verifier::validation, BlobRef, Cell, CellIndex, CellRef, CellsAndProofs,
+    DASContext, KZGCommitment, KZGProof, RowIndex,
+};
+
+/// A cell in the extended blob matrix, along with its proof and its position in the matrix.
+///
+/// The matching container in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/_features/eip7594/das-core.md#matrixentry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixEntry {
+    pub cell: Cell,
+    pub kzg_proof: KZGProof,
+    pub column_index: CellIndex,
+    pub row_index: RowIndex,
+}
+
+/// A column of the extended blob matrix.
+///
+/// This contains the cell and the proof at `index` for every blob, in the order of the blobs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataColumn {
+    pub index: CellIndex,
+    pub cells: Vec<Cell>,
+    pub proofs: Vec<KZGProof>,
+}
+
+/// The extended blob matrix of a block, split into columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataColumns {
+    /// The commitment to each blob, in the order of the blobs.
+    pub commitments: Vec<KZGCommitment>,
+    /// The columns of the matrix, where the column at position `i` has index `i`.
+    pub columns: Vec<DataColumn>,
+}
+
+impl DASContext {
+    /// Computes the extended blob matrix for the given blobs.
+    ///
+    /// The entries are ordered by row and then by column, ie all of the cells of the first
+    /// blob are returned before the cells of the second blob.
+    ///
+    /// Returns an error if any of the blobs is malformed.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/_features/eip7594/das-core.md#compute_matrix
+    pub fn compute_matrix(&self, blobs: &[BlobRef]) -> Result<Vec<MatrixEntry>, Error> {
+        let rows = self.compute_rows(blobs)?;
+
+        Ok(rows_to_matrix(rows))
+    }
+
+    /// Computes the extended blob matrix for the given blobs, split into columns, along
+    /// with the commitment to each blob.
+    ///
+    /// This is the data needed to create the data column sidecars of a block, where each
+    /// column is sent on its own subnet.
+    ///
+    /// Returns an error if any of the blobs is malformed.
+    pub fn compute_data_columns(&self, blobs: &[BlobRef]) -> Result<DataColumns, Error> {
+        let commitments = self.thread_pool.install(|| {
+            blobs
+                .par_iter()
+                .map(|blob| self.blob_to_kzg_commitment(blob))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        let rows = self.compute_rows(blobs)?;
+
+        // Transpose the rows into columns
+        let mut columns: Vec<_> = (0..self.config().cells_per_ext_blob())
+            .map(|index| DataColumn {
+                index: index as CellIndex,
+                cells: Vec::with_capacity(blobs.len()),
+                proofs: Vec::with_capacity(blobs.len()),
+            })
+            .collect();
+        for (cells, proofs) in rows {
+            for ((column, cell), proof) in columns.iter_mut().zip(cells).zip(proofs) {
+                column.cells.push(cell);
+                column.proofs.push(proof);
+            }
+        }
+
+        Ok(DataColumns {
+            commitments,
+            columns,
+        })
+    }
+
+    /// Recovers the extended blob matrix from a subset of its columns.
+    ///
+    /// `columns[i]` contains the cell at `column_indices[i]` for every blob, in the order of
+    /// the blobs. The entries are returned in the same order as `compute_matrix`.
+    ///
+    /// Since the same cells are missing from every row, the work that only depends on the
+    /// missing cells is done once for the whole matrix, rather than once per row.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/_features/eip7594/das-core.md#recover_matrix
+    pub fn recover_matrix(
+        &self,
+        columns: Vec<Vec<CellRef>>,
+        column_indices: Vec<CellIndex>,
+    ) -> Result<Vec<MatrixEntry>, Error> {
+        self.thread_pool.install(|| {
+            // Validation
+            //
+            validation::recover_matrix(self.config(), &column_indices, &columns)?;
+
+            // Transpose the columns into rows
+            let num_rows = columns.first().map_or(0, Vec::len);
+            let mut rows = vec![Vec::with_capacity(columns.len()); num_rows];
+            for column in columns {
+                for (row, cell) in rows.iter_mut().zip(column) {
+                    row.push(cell);
+                }
+            }
+
+            // Recover polynomials
+            //
+            let poly_coeffs = self.recover_rows_polynomial_coeff(column_indices, rows)?;
+
+            // Compute proofs and evaluation sets
+            //
+            let rows = poly_coeffs
+                .into_par_iter()
+                .map(|poly_coeff| self.compute_cells_and_kzg_proofs_from_poly_coeff(poly_coeff))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(rows_to_matrix(rows))
+        })
+    }
+
+    /// Computes the cells and proofs for each blob, failing if any of the blobs is malformed.
+    fn compute_rows(&self, blobs: &[BlobRef]) -> Result<Vec<CellsAndProofs>, Error> {
+        self.compute_cells_and_kzg_proofs_batch(blobs)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Flattens the cells and proofs of each row into the entries of the matrix, ordered by row
+/// and then by column.
+fn rows_to_matrix(rows: Vec<CellsAndProofs>) -> Vec<MatrixEntry> {
+    rows.into_iter()
+        .enumerate()
+        .flat_map(|(row_index, (cells, proofs))| {
+            cells.into_iter().zip(proofs).enumerate().map(
+                move |(column_index, (cell, kzg_proof))| MatrixEntry {
+                    cell,
+                    kzg_proof,
+                    column_index: column_index as CellIndex,
+                    row_index: row_index as RowIndex,
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB},
+        errors::VerifierError,
+        test_utils::dummy_blobs,
+    };
+
+    #[test]
+    fn matrix_and_columns_match_single_blob_results() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+        let rows: Vec<_> = blob_refs
+            .iter()
+            .map(|blob| ctx.compute_cells_and_kzg_proofs(blob).unwrap())
+            .collect();
+
+        let matrix = ctx.compute_matrix(&blob_refs).unwrap();
+        assert_eq!(matrix.len(), blobs.len() * CELLS_PER_EXT_BLOB);
+        for (i, entry) in matrix.iter().enumerate() {
+            let row_index = i / CELLS_PER_EXT_BLOB;
+            let column_index = i % CELLS_PER_EXT_BLOB;
+            assert_eq!(entry.row_index, row_index as RowIndex);
+            assert_eq!(entry.column_index, column_index as CellIndex);
+            assert_eq!(entry.cell, rows[row_index].0[column_index]);
+            assert_eq!(entry.kzg_proof, rows[row_index].1[column_index]);
+        }
+
+        let data_columns = ctx.compute_data_columns(&blob_refs).unwrap();
+        for (blob, commitment) in blob_refs.iter().zip(&data_columns.commitments) {
+            assert_eq!(&ctx.blob_to_kzg_commitment(blob).unwrap(), commitment);
+        }
+        assert_eq!(data_columns.columns.len(), CELLS_PER_EXT_BLOB);
+        for (column_index, column) in data_columns.columns.iter().enumerate() {
+            assert_eq!(column.index, column_index as CellIndex);
+            assert_eq!(column.cells.len(), blobs.len());
+            for (row_index, (cells, proofs)) in rows.iter().enumerate() {
+                assert_eq!(column.cells[row_index], cells[column_index]);
+                assert_eq!(column.proofs[row_index], proofs[column_index]);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_with_malformed_blob_is_rejected() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(1);
+        let malformed_blob = vec![0xff; BYTES_PER_BLOB];
+        let blob_refs: Vec<BlobRef> = vec![
+            blobs[0].as_slice().try_into().unwrap(),
+            malformed_blob.as_slice().try_into().unwrap(),
+        ];
+
+        assert!(ctx.compute_matrix(&blob_refs).is_err());
+        assert!(ctx.compute_data_columns(&blob_refs).is_err());
+    }
+
+    #[test]
+    fn matrix_of_no_blobs_is_empty() {
+        let ctx = DASContext::default();
+
+        assert!(ctx.compute_matrix(&[]).unwrap().is_empty());
+
+        let data_columns = ctx.compute_data_columns(&[]).unwrap();
+        assert!(data_columns.commitments.is_empty());
+        assert!(data_columns
+            .columns
+            .iter()
+            .all(|column| column.cells.is_empty() && column.proofs.is_empty()));
+    }
+
+    #[test]
+    fn recover_matrix_from_half_of_the_columns() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+        let matrix = ctx.compute_matrix(&blob_refs).unwrap();
+        let data_columns = ctx.compute_data_columns(&blob_refs).unwrap();
+
+        // Keep every other column
+        let (column_indices, columns): (Vec<_>, Vec<_>) = data_columns
+            .columns
+            .iter()
+            .step_by(2)
+            .map(|column| {
+                let cells: Vec<CellRef> = column.cells.iter().map(|cell| cell.as_ref()).collect();
+                (column.index, cells)
+            })
+            .unzip();
+
+        let recovered_matrix = ctx.recover_matrix(columns, column_indices).unwrap();
+        assert_eq!(recovered_matrix, matrix);
+    }
+
+    #[test]
+    fn recover_matrix_with_invalid_columns_is_rejected() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(2);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+        let data_columns = ctx.compute_data_columns(&blob_refs).unwrap();
+        fn column_cells(column: &DataColumn) -> Vec<CellRef<'_>> {
+            column.cells.iter().map(|cell| cell.as_ref()).collect()
+        }
+
+        // Not enough columns
+        let column_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as CellIndex / 2 - 1).collect();
+        let columns: Vec<_> = data_columns.columns[..column_indices.len()]
+            .iter()
+            .map(column_cells)
+            .collect();
+        assert!(matches!(
+            ctx.recover_matrix(columns, column_indices),
+            Err(Error::Verifier(
+                VerifierError::NotEnoughCellsToReconstruct { .. }
+            ))
+        ));
+
+        // A column that is missing a cell
+        let column_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as CellIndex / 2).collect();
+        let mut columns: Vec<_> = data_columns.columns[..column_indices.len()]
+            .iter()
+            .map(column_cells)
+            .collect();
+        columns[3].pop();
+        assert!(matches!(
+            ctx.recover_matrix(columns, column_indices),
+            Err(Error::Verifier(
+                VerifierError::ColumnsHaveDifferentLengths {
+                    column_index: 3,
+                    num_cells: 1,
+                    expected_num_cells: 2,
+                }
+            ))
+        ));
+    }
+}
@@ -1,63 +1,137 @@
+use std::collections::BTreeSet;
+
+use bls12_381::{ff::Field, Scalar};
 use kzg_multi_open::{
     commit_key::CommitKey,
-    {Prover, ProverInput},
+    reverse_bit_order,
+    single_point::SinglePointProver,
+    {PrecomputationLevel, Prover, ProverInput},
 };
+use rayon::prelude::*;
 
 use crate::{
-    constants::{
-        CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL,
-        FIELD_ELEMENTS_PER_EXT_BLOB,
-    },
-    errors::Error,
+    config::DASConfig,
+    constants::{BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB},
+    errors::{ConfigError, Error, ProverError, VerifierError},
     serialization::{
-        deserialize_blob_to_scalars, serialize_cells_and_proofs, serialize_g1_compressed,
+        coset_evaluations_to_cells, deserialize_blob_to_scalars, deserialize_compressed_g1,
+        deserialize_scalar, serialize_cells_and_proofs, serialize_g1_compressed,
     },
-    trusted_setup::TrustedSetup,
-    BlobRef, Cell, CellIndex, CellRef, KZGCommitment, KZGProof, DASContext,
+    trusted_setup::{load_embedded, TrustedSetupRef},
+    verifier::{compute_challenge, validation},
+    BlobRef, Bytes32Ref, Bytes48Ref, Cell, CellIndex, CellRef, CellsAndProofs, DASContext,
+    KZGCommitment, KZGProof, SerializedScalar,
 };
 
+mod precomputation;
+
 /// Context object that is used to call functions in the prover API.
 /// This includes, computing the commitments, proofs and cells.
 #[derive(Debug)]
 pub struct ProverContext {
-    kzg_multipoint_prover: Prover,
+    pub(crate) kzg_multipoint_prover: Prover,
+    kzg_single_point_prover: SinglePointProver,
+    /// Digest of the trusted setup that this context was created from.
+    ///
+    /// This is used to check that a serialized precomputation matches the trusted setup.
+    trusted_setup_digest: [u8; 32],
 }
 
 impl Default for ProverContext {
     fn default() -> Self {
-        let trusted_setup = TrustedSetup::default();
-        Self::new(&trusted_setup)
+        let trusted_setup = load_embedded();
+        Self::new(
+            &trusted_setup,
+            DASConfig::default(),
+            PrecomputationLevel::default(),
+        )
+        .expect("the embedded trusted setup supports the mainnet config")
     }
 }
 
 impl ProverContext {
-    pub fn new(trusted_setup: &TrustedSetup) -> Self {
-        let commit_key = CommitKey::from(trusted_setup);
+    /// Creates a new prover context for the given config.
+    ///
+    /// `precomputation` is the amount of precomputation to do for computing the proofs.
+    /// More precomputation makes computing proofs faster, at the cost of more memory.
+    ///
+    /// Returns an error if the trusted setup does not contain enough points for the config,
+    /// or if the window size of the precomputation is not supported.
+    pub fn new<'a>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        config: DASConfig,
+        precomputation: PrecomputationLevel,
+    ) -> Result<Self, ConfigError> {
+        let trusted_setup = trusted_setup.into();
+        config.validate(trusted_setup)?;
+        validate_precomputation_level(precomputation)?;
+
+        let commit_key = trusted_setup.commit_key();
 
         // The number of points that we will make an opening proof for,
         // ie a proof will attest to the value of a polynomial at these points.
-        let point_set_size = FIELD_ELEMENTS_PER_CELL;
+        let point_set_size = config.field_elements_per_cell();
 
         // The number of points that we will be making proofs for.
         //
         // Note: it is easy to calculate the number of proofs that we need to make
         // by doing number_of_points_to_open / point_set_size.
-        let number_of_points_to_open = FIELD_ELEMENTS_PER_EXT_BLOB;
+        let number_of_points_to_open = config.field_elements_per_ext_blob();
 
         let kzg_multipoint_prover = Prover::new(
-            commit_key,
-            FIELD_ELEMENTS_PER_BLOB,
+            commit_key.clone(),
+            config.field_elements_per_blob(),
             point_set_size,
             number_of_points_to_open,
+            precomputation,
         );
 
+        Ok(Self::with_multipoint_prover(
+            trusted_setup,
+            commit_key,
+            kzg_multipoint_prover,
+        ))
+    }
+
+    fn with_multipoint_prover(
+        trusted_setup: TrustedSetupRef,
+        commit_key: CommitKey,
+        kzg_multipoint_prover: Prover,
+    ) -> Self {
+        let kzg_single_point_prover = SinglePointProver::new(commit_key, FIELD_ELEMENTS_PER_BLOB);
+
         ProverContext {
             kzg_multipoint_prover,
+            kzg_single_point_prover,
+            trusted_setup_digest: trusted_setup.digest(),
+        }
+    }
+}
+
+/// Checks that the window size of the precomputation is non-zero and small enough for the
+/// precomputed tables to fit in memory.
+pub(crate) fn validate_precomputation_level(
+    precomputation: PrecomputationLevel,
+) -> Result<(), ConfigError> {
+    match precomputation {
+        PrecomputationLevel::WindowBits(window_bits) if !precomputation.is_valid() => {
+            Err(ConfigError::InvalidPrecomputationWindowBits {
+                window_bits,
+                max_window_bits: PrecomputationLevel::MAX_WINDOW_BITS,
+            })
         }
+        _ => Ok(()),
     }
 }
 
 impl DASContext {
+    /// Returns the prover context, or an error if this context was built without a prover.
+    pub(crate) fn prover(&self) -> Result<&ProverContext, ProverError> {
+        self.prover_ctx
+            .as_ref()
+            .ok_or(ProverError::ProverNotEnabled)
+    }
+
     /// Computes the KZG commitment to the polynomial represented by the blob.
     pub fn blob_to_kzg_commitment(&self, blob: BlobRef) -> Result<KZGCommitment, Error> {
         self.thread_pool.install(|| {
@@ -66,7 +140,7 @@ impl DASContext {
 
             // Compute commitment
             let commitment = self
-                .prover_ctx
+                .prover()?
                 .kzg_multipoint_prover
                 .commit(ProverInput::Data(scalars));
 
@@ -75,11 +149,99 @@ impl DASContext {
         })
     }
 
+    /// Computes a KZG proof attesting to the evaluation of the polynomial represented by
+    /// the blob at the point `z`.
+    ///
+    /// Returns the proof and the evaluation `y`.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#compute_kzg_proof
+    pub fn compute_kzg_proof(
+        &self,
+        blob: BlobRef,
+        z: Bytes32Ref,
+    ) -> Result<(KZGProof, SerializedScalar), Error> {
+        self.thread_pool.install(|| {
+            // Deserialization
+            //
+            let scalars = deserialize_blob_to_scalars(blob)?;
+            let z = deserialize_scalar(z)?;
+
+            // Computation
+            //
+            let (proof, y) = self
+                .prover()?
+                .kzg_single_point_prover
+                .compute_opening_proof(ProverInput::Data(scalars), z);
+
+            Ok((serialize_g1_compressed(&proof), y.to_bytes_be()))
+        })
+    }
+
+    /// Computes a KZG proof for the blob, at a challenge point derived from the blob and its commitment.
+    ///
+    /// This proof is used to verify that the blob is consistent with the commitment.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#compute_blob_kzg_proof
+    pub fn compute_blob_kzg_proof(
+        &self,
+        blob: BlobRef,
+        commitment: Bytes48Ref,
+    ) -> Result<KZGProof, Error> {
+        self.thread_pool.install(|| {
+            // Deserialization
+            //
+            let scalars = deserialize_blob_to_scalars(blob)?;
+            // The commitment is only used to compute the challenge, however we still
+            // need to check that it is a valid G1 point.
+            let _ = deserialize_compressed_g1(commitment)?;
+
+            // Computation
+            //
+            let z = compute_challenge(blob, commitment);
+            let (proof, _) = self
+                .prover()?
+                .kzg_single_point_prover
+                .compute_opening_proof(ProverInput::Data(scalars), z);
+
+            Ok(serialize_g1_compressed(&proof))
+        })
+    }
+
+    /// Computes the cells for the given blob, without computing the KZG proofs.
+    ///
+    /// This is cheaper than `compute_cells_and_kzg_proofs` when the proofs are not needed.
+    ///
+    /// Note: This does not use the prover, so it can also be called on a context that
+    /// was built without one.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/_features/eip7594/polynomial-commitments-sampling.md#compute_cells
+    pub fn compute_cells(&self, blob: BlobRef) -> Result<Vec<Cell>, Error> {
+        self.thread_pool.install(|| {
+            // Deserialization
+            //
+            let scalars = deserialize_blob_to_scalars(blob)?;
+
+            // Computation
+            //
+            // The blob is the bit-reversed evaluation form of the polynomial, so its systematic
+            // encoding is the extended blob in the order that it is split into cells.
+            let extended_blob = self
+                .verifier_ctx
+                .rs
+                .encode_systematic(scalars)
+                .map_err(VerifierError::from)?;
+
+            Ok(coset_evaluations_to_cells(extended_blob.chunks_exact(
+                self.verifier_ctx.config.field_elements_per_cell(),
+            )))
+        })
+    }
+
     /// Computes the cells and the KZG proofs for the given blob.
     pub fn compute_cells_and_kzg_proofs(
         &self,
         blob: BlobRef,
-    ) -> Result<([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]), Error> {
+    ) -> Result<(Vec<Cell>, Vec<KZGProof>), Error> {
         self.thread_pool.install(|| {
             // Deserialization
             //
@@ -88,7 +250,7 @@ impl DASContext {
             // Computation
             //
             let (proofs, cells) = self
-                .prover_ctx
+                .prover()?
                 .kzg_multipoint_prover
                 .compute_multi_opening_proofs(ProverInput::Data(scalars));
 
@@ -96,6 +258,111 @@ impl DASContext {
         })
     }
 
+    /// Computes the cells and the KZG proofs for each of the given blobs.
+    ///
+    /// The blobs are processed in parallel, in addition to the parallelism used when computing
+    /// the proofs for a single blob, so this is faster than calling `compute_cells_and_kzg_proofs`
+    /// for each blob.
+    ///
+    /// The results are returned in the same order as the blobs. If a blob is malformed, only
+    /// the result for that blob is an error.
+    pub fn compute_cells_and_kzg_proofs_batch(
+        &self,
+        blobs: &[BlobRef],
+    ) -> Vec<Result<CellsAndProofs, Error>> {
+        self.thread_pool.install(|| {
+            // Note: `compute_cells_and_kzg_proofs` runs directly on the current thread,
+            // since we are already inside of the thread pool.
+            blobs
+                .par_iter()
+                .map(|blob| self.compute_cells_and_kzg_proofs(blob))
+                .collect()
+        })
+    }
+
+    /// Recovers all of the cells, given a subset of cells, without computing the KZG proofs.
+    ///
+    /// This is cheaper than `recover_cells_and_proofs` when the proofs are not needed.
+    pub fn recover_cells(
+        &self,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+    ) -> Result<Vec<Cell>, Error> {
+        self.thread_pool.install(|| {
+            // Recover polynomial
+            //
+            let poly_coeff = self.recover_polynomial_coeff(cell_indices, cells)?;
+
+            // Compute evaluation sets
+            //
+            self.compute_cells_from_poly_coeff(poly_coeff)
+        })
+    }
+
+    /// Corrects the cells of an extended blob, when some of them may be corrupt, without
+    /// computing the KZG proofs.
+    ///
+    /// All of the cells must be given, ordered by their cell index. Returns the corrected cells,
+    /// along with the indices of the cells that were corrupt, in ascending order.
+    ///
+    /// Unlike `recover_cells`, the positions of the corrupt cells do not need to be known. Since
+    /// a corrupt cell usually has all of its field elements changed, this can correct up to
+    /// `(1 - 1 / extension_factor) / 2` of the cells, which is a quarter of the cells for mainnet.
+    ///
+    /// A cell with field elements that do not deserialize is also treated as corrupt.
+    pub fn decode_cells_with_errors(
+        &self,
+        cells: Vec<CellRef>,
+    ) -> Result<(Vec<Cell>, Vec<CellIndex>), Error> {
+        self.thread_pool.install(|| {
+            let config = &self.verifier_ctx.config;
+
+            // Validation
+            //
+            validation::decode_cells_with_errors(config, &cells)?;
+
+            // Deserialization
+            //
+            // A field element that is not canonical is replaced with zero, so that the decoder
+            // treats it as an error, and its cell is reported as corrupt.
+            let mut malformed_cell_indices = BTreeSet::new();
+            let mut codeword = Vec::with_capacity(config.field_elements_per_ext_blob());
+            for (cell_index, cell) in cells.iter().enumerate() {
+                for scalar_bytes in cell.chunks_exact(BYTES_PER_FIELD_ELEMENT) {
+                    let scalar = deserialize_scalar(scalar_bytes).unwrap_or_else(|_| {
+                        malformed_cell_indices.insert(cell_index as CellIndex);
+                        Scalar::ZERO
+                    });
+                    codeword.push(scalar);
+                }
+            }
+            // The cells are in bit-reversed order, relative to the evaluation domain
+            reverse_bit_order(&mut codeword);
+
+            // Computation
+            //
+            let (poly_coeff, error_positions) = self
+                .verifier_ctx
+                .rs
+                .decode_with_errors(codeword)
+                .map_err(VerifierError::from)?;
+
+            // The codeword is the bit-reversal of the cells, so this maps each position in
+            // the codeword to its position in the flattened cells.
+            let mut cell_positions: Vec<usize> =
+                (0..config.field_elements_per_ext_blob()).collect();
+            reverse_bit_order(&mut cell_positions);
+            let mut corrupt_cell_indices = malformed_cell_indices;
+            corrupt_cell_indices.extend(error_positions.into_iter().map(|position| {
+                (cell_positions[position] / config.field_elements_per_cell()) as CellIndex
+            }));
+
+            let cells = self.compute_cells_from_poly_coeff(poly_coeff)?;
+
+            Ok((cells, corrupt_cell_indices.into_iter().collect()))
+        })
+    }
+
     /// Recovers the cells and computes the KZG proofs, given a subset of cells.
     ///
     /// Use erasure decoding to recover the polynomial corresponding to the cells
@@ -107,7 +374,7 @@ impl DASContext {
         &self,
         cell_indices: Vec<CellIndex>,
         cells: Vec<CellRef>,
-    ) -> Result<([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]), Error> {
+    ) -> Result<(Vec<Cell>, Vec<KZGProof>), Error> {
         self.thread_pool.install(|| {
             // Recover polynomial
             //
@@ -115,12 +382,140 @@ impl DASContext {
 
             // Compute proofs and evaluation sets
             //
-            let (proofs, coset_evaluations) = self
-                .prover_ctx
-                .kzg_multipoint_prover
-                .compute_multi_opening_proofs(ProverInput::PolyCoeff(poly_coeff));
-
-            Ok(serialize_cells_and_proofs(coset_evaluations, proofs))
+            self.compute_cells_and_kzg_proofs_from_poly_coeff(poly_coeff)
         })
     }
+
+    /// Computes the cells for a polynomial in monomial form.
+    ///
+    /// Note: This does not use the prover, so that cells can also be recovered
+    /// using a context that was built without one.
+    fn compute_cells_from_poly_coeff(&self, poly_coeff: Vec<Scalar>) -> Result<Vec<Cell>, Error> {
+        let mut evaluations = self
+            .verifier_ctx
+            .rs
+            .encode(poly_coeff)
+            .map_err(VerifierError::from)?;
+        reverse_bit_order(&mut evaluations);
+
+        Ok(coset_evaluations_to_cells(evaluations.chunks_exact(
+            self.verifier_ctx.config.field_elements_per_cell(),
+        )))
+    }
+
+    /// Computes the cells and the KZG proofs for a polynomial in monomial form.
+    pub(crate) fn compute_cells_and_kzg_proofs_from_poly_coeff(
+        &self,
+        poly_coeff: Vec<Scalar>,
+    ) -> Result<CellsAndProofs, Error> {
+        let (proofs, coset_evaluations) = self
+            .prover()?
+            .kzg_multipoint_prover
+            .compute_multi_opening_proofs(ProverInput::PolyCoeff(poly_coeff));
+
+        Ok(serialize_cells_and_proofs(coset_evaluations, proofs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use erasure_codes::errors::RSError;
+
+    use crate::{
+        constants::{BYTES_PER_BLOB, BYTES_PER_CELL, CELLS_PER_EXT_BLOB},
+        errors::{Error, VerifierError},
+        test_utils::{dummy_blob, dummy_blobs},
+        Cell, CellRef, DASContext,
+    };
+
+    #[test]
+    fn compute_cells_and_kzg_proofs_batch_matches_single_blob() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(3);
+        // A blob with field elements that are not canonical
+        let malformed_blob = vec![0xff; BYTES_PER_BLOB];
+
+        let blob_refs = vec![
+            blobs[0].as_slice().try_into().unwrap(),
+            blobs[1].as_slice().try_into().unwrap(),
+            malformed_blob.as_slice().try_into().unwrap(),
+            blobs[2].as_slice().try_into().unwrap(),
+        ];
+
+        let results = ctx.compute_cells_and_kzg_proofs_batch(&blob_refs);
+        assert_eq!(results.len(), blob_refs.len());
+
+        for (blob, result) in blob_refs.into_iter().zip(results) {
+            match ctx.compute_cells_and_kzg_proofs(blob) {
+                Ok(expected) => assert_eq!(result.unwrap(), expected),
+                Err(_) => assert!(matches!(result, Err(Error::Serialization(_)))),
+            }
+        }
+
+        assert!(ctx.compute_cells_and_kzg_proofs_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn decode_cells_with_errors_reports_corrupt_cells() {
+        let ctx = DASContext::default();
+
+        let blob = dummy_blob(0);
+        let cells = ctx
+            .compute_cells(blob.as_slice().try_into().unwrap())
+            .unwrap();
+
+        // Note: The cells of the extension are used, since the cells of the dummy blob are all the same.
+        let corrupt_cells = |num_corrupt_cells: usize| -> Vec<Cell> {
+            let mut corrupted = cells.clone();
+            corrupted[64..64 + num_corrupt_cells]
+                .clone_from_slice(&cells[65..65 + num_corrupt_cells]);
+            corrupted
+        };
+        fn cell_refs(cells: &[Cell]) -> Vec<CellRef<'_>> {
+            cells.iter().map(AsRef::as_ref).collect()
+        }
+
+        let (decoded_cells, corrupt_cell_indices) =
+            ctx.decode_cells_with_errors(cell_refs(&cells)).unwrap();
+        assert_eq!(decoded_cells, cells);
+        assert!(corrupt_cell_indices.is_empty());
+
+        // Every field element in a corrupt cell is wrong, so up to a quarter of the cells can be corrupt
+        let max_num_corrupt_cells = CELLS_PER_EXT_BLOB / 4;
+        let corrupted = corrupt_cells(max_num_corrupt_cells);
+        let (decoded_cells, corrupt_cell_indices) =
+            ctx.decode_cells_with_errors(cell_refs(&corrupted)).unwrap();
+        assert_eq!(decoded_cells, cells);
+        assert_eq!(
+            corrupt_cell_indices,
+            (64..64 + max_num_corrupt_cells as u64).collect::<Vec<_>>()
+        );
+
+        // Cells with field elements that do not deserialize are corrected too
+        let mut corrupted = corrupt_cells(max_num_corrupt_cells - 1);
+        corrupted[3] = vec![0xff; BYTES_PER_CELL].into_boxed_slice();
+        let (decoded_cells, corrupt_cell_indices) =
+            ctx.decode_cells_with_errors(cell_refs(&corrupted)).unwrap();
+        assert_eq!(decoded_cells, cells);
+        let mut expected_indices = vec![3];
+        expected_indices.extend(64..64 + max_num_corrupt_cells as u64 - 1);
+        assert_eq!(corrupt_cell_indices, expected_indices);
+
+        let corrupted = corrupt_cells(max_num_corrupt_cells + 1);
+        assert!(matches!(
+            ctx.decode_cells_with_errors(cell_refs(&corrupted)),
+            Err(Error::Verifier(VerifierError::ReedSolomon(
+                RSError::TooManyErrors { .. }
+            )))
+        ));
+
+        // All of the cells are needed
+        assert!(matches!(
+            ctx.decode_cells_with_errors(cell_refs(&cells[1..])),
+            Err(Error::Verifier(
+                VerifierError::NotEnoughCellsToReconstruct { .. }
+            ))
+        ));
+    }
 }
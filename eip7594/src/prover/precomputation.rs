@@ -0,0 +1,225 @@
+//! Serialization of the precomputation done by the prover.
+//!
+//! Creating a `ProverContext` requires FFTing the SRS vectors over G1 and building fixed
+//! base MSM tables for each of them, which dominates the time needed to create the context.
+//! This module allows that precomputation to be saved and loaded instead.
+//!
+// The layout is the following, where all integers are big-endian:
+/*
+    magic                   8 bytes             `ETHKZGPC`
+    version                 4 bytes
+    trusted_setup_digest    32 bytes            see `TrustedSetupRef::digest`
+    precomputation          remaining bytes     see `Prover::precomputation_to_bytes`
+    checksum                32 bytes            sha256 of all of the preceding bytes
+*/
+
+use std::path::Path;
+
+use kzg_multi_open::Prover;
+use sha2::{Digest, Sha256};
+
+use super::ProverContext;
+use crate::{config::DASConfig, errors::PrecomputationError, trusted_setup::TrustedSetupRef};
+
+/// Magic bytes at the start of a serialized precomputation.
+const MAGIC: &[u8; 8] = b"ETHKZGPC";
+/// The version of the encoding that this module reads and writes.
+const VERSION: u32 = 1;
+/// The size of the trusted setup digest.
+const DIGEST_SIZE: usize = 32;
+/// The size of the header: magic, version and the trusted setup digest.
+const HEADER_SIZE: usize = MAGIC.len() + 4 + DIGEST_SIZE;
+/// The size of the sha256 checksum at the end of the encoding.
+const CHECKSUM_SIZE: usize = 32;
+
+impl ProverContext {
+    /// Serializes the precomputation done when creating this context, so that it can be
+    /// loaded with `from_precomputation_bytes`.
+    pub fn precomputation_to_bytes(&self) -> Vec<u8> {
+        let precomputation = self.kzg_multipoint_prover.precomputation_to_bytes();
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + precomputation.len() + CHECKSUM_SIZE);
+        bytes.extend(MAGIC);
+        bytes.extend(VERSION.to_be_bytes());
+        bytes.extend(self.trusted_setup_digest);
+        bytes.extend(precomputation);
+
+        let checksum: [u8; CHECKSUM_SIZE] = Sha256::digest(&bytes).into();
+        bytes.extend(checksum);
+
+        bytes
+    }
+
+    /// Creates a `ProverContext` using a precomputation that was serialized with
+    /// `precomputation_to_bytes`, instead of recomputing it.
+    ///
+    /// Returns an error if the precomputation is malformed or was created from a
+    /// different trusted setup or config.
+    ///
+    /// Note: The checksum only protects against accidental corruption, so this method
+    /// should only be used with bytes that come from a trusted source.
+    pub fn from_precomputation_bytes<'a>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        config: DASConfig,
+        bytes: &[u8],
+    ) -> Result<ProverContext, PrecomputationError> {
+        let trusted_setup = trusted_setup.into();
+        if bytes.len() < HEADER_SIZE + CHECKSUM_SIZE {
+            return Err(PrecomputationError::MalformedBytes {
+                reason: format!("expected at least {} bytes", HEADER_SIZE + CHECKSUM_SIZE),
+            });
+        }
+
+        let (payload, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+        let (header, precomputation) = payload.split_at(HEADER_SIZE);
+
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(PrecomputationError::MalformedBytes {
+                reason: "expected the precomputation to start with the magic bytes".to_string(),
+            });
+        }
+        let version = u32::from_be_bytes(
+            header[MAGIC.len()..MAGIC.len() + 4]
+                .try_into()
+                .expect("slice has 4 bytes"),
+        );
+        if version != VERSION {
+            return Err(PrecomputationError::UnsupportedVersion { version });
+        }
+
+        let expected_checksum: [u8; CHECKSUM_SIZE] = Sha256::digest(payload).into();
+        if checksum != expected_checksum {
+            return Err(PrecomputationError::ChecksumMismatch);
+        }
+
+        if header[MAGIC.len() + 4..] != trusted_setup.digest() {
+            return Err(PrecomputationError::TrustedSetupMismatch);
+        }
+        config.validate(trusted_setup)?;
+
+        let commit_key = trusted_setup.commit_key();
+        let kzg_multipoint_prover = Prover::from_precomputation_bytes(
+            commit_key.clone(),
+            config.field_elements_per_blob(),
+            config.field_elements_per_cell(),
+            config.field_elements_per_ext_blob(),
+            precomputation,
+        )
+        .ok_or_else(|| PrecomputationError::MalformedBytes {
+            reason: "could not deserialize the FK20 precomputation".to_string(),
+        })?;
+
+        Ok(Self::with_multipoint_prover(
+            trusted_setup,
+            commit_key,
+            kzg_multipoint_prover,
+        ))
+    }
+
+    /// Creates a `ProverContext` using a precomputation that was saved to a file.
+    ///
+    /// See `from_precomputation_bytes` for more details.
+    pub fn from_precomputation_file<'a, P: AsRef<Path>>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        config: DASConfig,
+        path: P,
+    ) -> Result<ProverContext, PrecomputationError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_precomputation_bytes(trusted_setup, config, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::dummy_blob, DASContext, PrecomputationLevel, TrustedSetup};
+
+    #[test]
+    fn precomputation_round_trip() {
+        let trusted_setup = TrustedSetup::default();
+        let config = DASConfig::default();
+        let mut ctx = DASContext::default();
+
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+        let expected = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        for precomputation in [PrecomputationLevel::None, PrecomputationLevel::default()] {
+            let bytes = ProverContext::new(&trusted_setup, config, precomputation)
+                .unwrap()
+                .precomputation_to_bytes();
+
+            ctx.prover_ctx = Some(
+                ProverContext::from_precomputation_bytes(&trusted_setup, config, &bytes).unwrap(),
+            );
+            let got = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn precomputation_for_different_trusted_setup_is_rejected() {
+        let config = DASConfig::default();
+        let bytes = ProverContext::new(&TrustedSetup::default(), config, PrecomputationLevel::None)
+            .unwrap()
+            .precomputation_to_bytes();
+
+        let mut trusted_setup = TrustedSetup::default();
+        trusted_setup.g1_monomial.swap(0, 1);
+
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &bytes),
+            Err(PrecomputationError::TrustedSetupMismatch)
+        ));
+    }
+
+    #[test]
+    fn malformed_precomputation_is_rejected() {
+        let trusted_setup = TrustedSetup::default();
+        let config = DASConfig::default();
+        let bytes = ProverContext::new(&trusted_setup, config, PrecomputationLevel::None)
+            .unwrap()
+            .precomputation_to_bytes();
+
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &bytes[..10]),
+            Err(PrecomputationError::MalformedBytes { .. })
+        ));
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &wrong_magic),
+            Err(PrecomputationError::MalformedBytes { .. })
+        ));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[MAGIC.len() + 3] = 2;
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &wrong_version),
+            Err(PrecomputationError::UnsupportedVersion { version: 2 })
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupted[HEADER_SIZE + 100] ^= 1;
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &corrupted),
+            Err(PrecomputationError::ChecksumMismatch)
+        ));
+
+        // A truncated precomputation with a valid checksum should still be rejected
+        let mut truncated = bytes[..bytes.len() - CHECKSUM_SIZE - 96].to_vec();
+        let checksum: [u8; CHECKSUM_SIZE] = Sha256::digest(&truncated).into();
+        truncated.extend(checksum);
+        assert!(matches!(
+            ProverContext::from_precomputation_bytes(&trusted_setup, config, &truncated),
+            Err(PrecomputationError::MalformedBytes { .. })
+        ));
+
+        assert!(matches!(
+            ProverContext::from_precomputation_file(&trusted_setup, config, "does_not_exist.bin"),
+            Err(PrecomputationError::Io(_))
+        ));
+    }
+}
@@ -1,9 +1,6 @@
 use crate::{
-    constants::{
-        BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1_POINT,
-        CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_CELL,
-    },
-    Cell, KZGProof,
+    constants::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1_POINT},
+    Cell, CellRef, KZGProof,
 };
 use bls12_381::{G1Point, Scalar};
 
@@ -87,13 +84,7 @@ pub(crate) fn deserialize_compressed_g1_points(
 }
 
 pub(crate) fn serialize_scalars_to_cell(scalars: &[Scalar]) -> Vec<u8> {
-    assert_eq!(
-        scalars.len(),
-        FIELD_ELEMENTS_PER_CELL,
-        "must have exactly {FIELD_ELEMENTS_PER_CELL} scalars to serialize to a cell"
-    );
-
-    let mut bytes = Vec::with_capacity(FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT);
+    let mut bytes = Vec::with_capacity(scalars.len() * BYTES_PER_FIELD_ELEMENT);
     for scalar in scalars {
         bytes.extend_from_slice(&scalar.to_bytes_be());
     }
@@ -101,45 +92,29 @@ pub(crate) fn serialize_scalars_to_cell(scalars: &[Scalar]) -> Vec<u8> {
 }
 
 pub(crate) fn deserialize_cells(
-    cells: Vec<&[u8; BYTES_PER_CELL]>,
+    cells: Vec<CellRef>,
 ) -> Result<Vec<Vec<Scalar>>, SerializationError> {
-    cells
-        .into_iter()
-        .map(AsRef::as_ref)
-        .map(deserialize_cell_to_scalars)
-        .collect()
+    cells.into_iter().map(deserialize_cell_to_scalars).collect()
 }
 
 /// Converts a set of scalars (evaluations) to the `Cell` type.
 pub(crate) fn coset_evaluations_to_cells<T: AsRef<[Scalar]>>(
     evaluations: impl Iterator<Item = T>,
-) -> [Cell; CELLS_PER_EXT_BLOB] {
-    let cells: Vec<Cell> = evaluations
-        .map(|eval| serialize_scalars_to_cell(eval.as_ref()))
-        .map(|cell| {
-            cell.into_boxed_slice()
-                .try_into()
-                .expect("infallible: Vec<u8> should have length equal to BYTES_PER_CELL")
-        })
-        .collect();
-
-    cells
-        .try_into()
-        .unwrap_or_else(|_| panic!("expected {} number of cells", CELLS_PER_EXT_BLOB))
+) -> Vec<Cell> {
+    evaluations
+        .map(|eval| serialize_scalars_to_cell(eval.as_ref()).into_boxed_slice())
+        .collect()
 }
 
 pub(crate) fn serialize_cells_and_proofs(
     coset_evaluations: Vec<Vec<Scalar>>,
     proofs: Vec<G1Point>,
-) -> ([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]) {
+) -> (Vec<Cell>, Vec<KZGProof>) {
     // Serialize the evaluation sets into `Cell`s.
     let cells = coset_evaluations_to_cells(coset_evaluations.into_iter());
 
     // Serialize the proofs into `KZGProof` objects.
-    let proofs: Vec<_> = proofs.iter().map(serialize_g1_compressed).collect();
-    let proofs: [KZGProof; CELLS_PER_EXT_BLOB] = proofs
-        .try_into()
-        .unwrap_or_else(|_| panic!("expected {} number of proofs", CELLS_PER_EXT_BLOB));
+    let proofs = proofs.iter().map(serialize_g1_compressed).collect();
 
     (cells, proofs)
 }
@@ -0,0 +1,16 @@
+use crate::constants::BYTES_PER_BLOB;
+
+/// Returns a blob where every field element is canonical, since the first byte of each
+/// field element is zero.
+///
+/// Blobs that are created using different seeds are different.
+pub(crate) fn dummy_blob(seed: u8) -> Vec<u8> {
+    (0..BYTES_PER_BLOB)
+        .map(|i| if i % 32 == 0 { 0 } else { (i as u8) ^ seed })
+        .collect()
+}
+
+/// Returns `num_blobs` different dummy blobs.
+pub(crate) fn dummy_blobs(num_blobs: u8) -> Vec<Vec<u8>> {
+    (0..num_blobs).map(dummy_blob).collect()
+}
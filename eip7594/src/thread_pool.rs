@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use rayon::ThreadPool;
+
+/// The thread pool that the methods of a `DASContext` are run on.
+#[derive(Debug, Clone)]
+pub(crate) enum ContextThreadPool {
+    /// A thread pool that was created for the context, or that is shared with the caller.
+    Explicit(Arc<ThreadPool>),
+    /// The thread pool of the caller.
+    ///
+    /// If the caller is not running inside of a rayon thread pool, this is the global rayon thread pool.
+    Ambient,
+}
+
+impl ContextThreadPool {
+    /// Executes `op` within the thread pool.
+    pub(crate) fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match self {
+            ContextThreadPool::Explicit(thread_pool) => thread_pool.install(op),
+            ContextThreadPool::Ambient => op(),
+        }
+    }
+}
@@ -1,12 +1,26 @@
-use bls12_381::{G1Point, G2Point};
+use std::path::Path;
+
+use bls12_381::{g1_batch_normalize, G1Point, G1Projective, G2Point};
 use kzg_multi_open::{commit_key::CommitKey, opening_key::OpeningKey};
-use serde::Deserialize;
+use polynomial::domain::Domain;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL},
+    errors::TrustedSetupError,
+};
+
+mod binary;
 
-use crate::constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL};
+pub use binary::DecodedTrustedSetup;
 
+#[cfg(any(test, not(feature = "binary_trusted_setup")))]
 const TRUSTED_SETUP_JSON: &str = include_str!("../data/trusted_setup_4096.json");
+#[cfg(any(test, feature = "binary_trusted_setup"))]
+const TRUSTED_SETUP_BYTES: &[u8] = include_bytes!("../data/trusted_setup_4096.bin");
 
-#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct TrustedSetup {
     pub g1_monomial: Vec<String>,
     pub g1_lagrange: Vec<String>,
@@ -26,15 +40,126 @@ enum SubgroupCheck {
     NoCheck,
 }
 
+/// The number of G1 points, in monomial and in lagrange form, that the trusted setup must contain.
+const NUM_G1_POINTS: usize = FIELD_ELEMENTS_PER_BLOB;
+/// The number of G2 points that the trusted setup must contain.
+///
+/// The verifier needs one more point than the number of points in a cell, in order to commit
+/// to the vanishing polynomial of a coset.
+const NUM_G2_POINTS: usize = FIELD_ELEMENTS_PER_CELL + 1;
+
+/// Json representation of the trusted setup, where every field is optional.
+///
+/// This allows us to report which arrays are missing, instead of a generic parsing error.
+#[derive(Deserialize)]
+struct TrustedSetupJson {
+    g1_monomial: Option<Vec<String>>,
+    g1_lagrange: Option<Vec<String>>,
+    g2_monomial: Option<Vec<String>>,
+}
+
+// Note: The conversions below panic if the trusted setup is malformed.
+// Use `TrustedSetup::try_from_json` to catch these errors when loading the trusted setup.
 impl From<&TrustedSetup> for CommitKey {
     fn from(setup: &TrustedSetup) -> Self {
-        setup.to_commit_key(SubgroupCheck::NoCheck)
+        setup
+            .to_commit_key(SubgroupCheck::NoCheck)
+            .expect("trusted setup has malformed g1 points")
     }
 }
 
 impl From<&TrustedSetup> for OpeningKey {
     fn from(setup: &TrustedSetup) -> Self {
-        setup.to_opening_key(SubgroupCheck::NoCheck)
+        setup.opening_key(FIELD_ELEMENTS_PER_CELL)
+    }
+}
+
+/// A reference to a trusted setup, in either of the forms that a context can be created from.
+///
+/// Creating a context from a `DecodedTrustedSetup` skips decoding the hex encoded points.
+#[derive(Debug, Clone, Copy)]
+pub enum TrustedSetupRef<'a> {
+    Hex(&'a TrustedSetup),
+    Decoded(&'a DecodedTrustedSetup),
+}
+
+impl<'a> From<&'a TrustedSetup> for TrustedSetupRef<'a> {
+    fn from(trusted_setup: &'a TrustedSetup) -> Self {
+        TrustedSetupRef::Hex(trusted_setup)
+    }
+}
+
+impl<'a> From<&'a DecodedTrustedSetup> for TrustedSetupRef<'a> {
+    fn from(trusted_setup: &'a DecodedTrustedSetup) -> Self {
+        TrustedSetupRef::Decoded(trusted_setup)
+    }
+}
+
+impl TrustedSetupRef<'_> {
+    /// Returns the number of G2 points in the trusted setup.
+    pub(crate) fn num_g2_points(&self) -> usize {
+        match self {
+            TrustedSetupRef::Hex(trusted_setup) => trusted_setup.g2_monomial.len(),
+            TrustedSetupRef::Decoded(trusted_setup) => trusted_setup.g2_monomial.len(),
+        }
+    }
+
+    /// Creates the commit key used to commit to blobs and compute proofs.
+    pub(crate) fn commit_key(&self) -> CommitKey {
+        match self {
+            TrustedSetupRef::Hex(trusted_setup) => CommitKey::from(*trusted_setup),
+            TrustedSetupRef::Decoded(trusted_setup) => CommitKey::from(*trusted_setup),
+        }
+    }
+
+    /// Creates the opening key used to verify proofs for cells of `field_elements_per_cell` points.
+    pub(crate) fn opening_key(&self, field_elements_per_cell: usize) -> OpeningKey {
+        match self {
+            TrustedSetupRef::Hex(trusted_setup) => {
+                trusted_setup.opening_key(field_elements_per_cell)
+            }
+            TrustedSetupRef::Decoded(trusted_setup) => {
+                trusted_setup.opening_key(field_elements_per_cell)
+            }
+        }
+    }
+
+    /// Computes a sha256 digest that identifies the points in the trusted setup.
+    ///
+    /// The hex encoded points are normalized by removing the `0x` prefix and lowercasing
+    /// them, so that the same setup loaded from different formats has the same digest.
+    /// The decoded points are hashed as the hex encoding of their compressed form, so that
+    /// they have the same digest as the hex encoded points that they were decoded from.
+    pub(crate) fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        match self {
+            TrustedSetupRef::Hex(trusted_setup) => {
+                for points in [
+                    &trusted_setup.g1_monomial,
+                    &trusted_setup.g1_lagrange,
+                    &trusted_setup.g2_monomial,
+                ] {
+                    hasher.update((points.len() as u64).to_be_bytes());
+                    for point in points {
+                        let point = point.strip_prefix("0x").unwrap_or(point);
+                        hasher.update(point.to_ascii_lowercase());
+                    }
+                }
+            }
+            TrustedSetupRef::Decoded(trusted_setup) => {
+                for points in [&trusted_setup.g1_monomial, &trusted_setup.g1_lagrange] {
+                    hasher.update((points.len() as u64).to_be_bytes());
+                    for point in points {
+                        hasher.update(hex::encode(point.to_compressed()));
+                    }
+                }
+                hasher.update((trusted_setup.g2_monomial.len() as u64).to_be_bytes());
+                for point in &trusted_setup.g2_monomial {
+                    hasher.update(hex::encode(point.to_compressed()));
+                }
+            }
+        }
+        hasher.finalize().into()
     }
 }
 
@@ -62,10 +187,134 @@ impl TrustedSetup {
     }
     */
     pub fn from_json(json: &str) -> TrustedSetup {
-        let trusted_setup = Self::from_json_unchecked(json);
-        trusted_setup.validate_trusted_setup();
-        trusted_setup
+        // Note: it is fine to panic here since this method is called on startup
+        // and we want to fail fast if the trusted setup is malformed.
+        Self::try_from_json(json).expect("could not load trusted setup")
     }
+
+    /// Parse a Json string in the format specified by the ethereum trusted setup.
+    ///
+    /// Returns an error if the trusted setup is malformed, this includes:
+    /// - Missing arrays of points or arrays with an unexpected number of points
+    /// - Points that are not valid hex strings or have the wrong length
+    /// - Points that are not on the curve or not in the correct subgroup
+    ///
+    /// See `from_json` for the expected format.
+    pub fn try_from_json(json: &str) -> Result<TrustedSetup, TrustedSetupError> {
+        let json: TrustedSetupJson =
+            serde_json::from_str(json).map_err(|err| TrustedSetupError::MalformedJson {
+                reason: err.to_string(),
+            })?;
+
+        let trusted_setup = TrustedSetup {
+            g1_monomial: json.g1_monomial.ok_or(TrustedSetupError::MissingField {
+                field: "g1_monomial",
+            })?,
+            g1_lagrange: json.g1_lagrange.ok_or(TrustedSetupError::MissingField {
+                field: "g1_lagrange",
+            })?,
+            g2_monomial: json.g2_monomial.ok_or(TrustedSetupError::MissingField {
+                field: "g2_monomial",
+            })?,
+        };
+        trusted_setup.validate_trusted_setup()?;
+
+        Ok(trusted_setup)
+    }
+
+    /// Reads the file at `path` and parses it as a Json trusted setup.
+    ///
+    /// See `try_from_json` for the errors that can be returned.
+    pub fn try_from_file<P: AsRef<Path>>(path: P) -> Result<TrustedSetup, TrustedSetupError> {
+        let json = std::fs::read_to_string(path)?;
+        Self::try_from_json(&json)
+    }
+
+    /// Parse a txt string in the format used by c-kzg-4844.
+    ///
+    /// The file that c-kzg-4844 ships is located here: https://github.com/ethereum/c-kzg-4844/blob/main/src/trusted_setup.txt
+    ///
+    /// Both the current format and the legacy format, which does not contain the G1 points
+    /// in monomial form, are accepted. For the legacy format, the monomial G1 points are
+    /// derived from the lagrange G1 points.
+    ///
+    // The format is a list of whitespace separated values, usually one per line:
+    /*
+    4096                                                  <- number of G1 points
+    65                                                    <- number of G2 points
+    a0413c0dcafec6dbc9f47d66785cf1e8c981044f7d13cfe3e...  <- G1 points in lagrange form
+    ...
+    93e02b6052719f607dacd3a088274f65596bd0d09920b61ab...  <- G2 points in monomial form
+    ...
+    97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a...  <- G1 points in monomial form (not in legacy format)
+    ...
+    */
+    pub fn try_from_txt(txt: &str) -> Result<TrustedSetup, TrustedSetupError> {
+        let mut tokens = txt.split_whitespace();
+
+        let num_g1_points = parse_txt_count(tokens.next(), "number of G1 points")?;
+        let num_g2_points = parse_txt_count(tokens.next(), "number of G2 points")?;
+
+        let g1_lagrange = take_txt_points(&mut tokens, num_g1_points, "g1_lagrange")?;
+        let g2_monomial = take_txt_points(&mut tokens, num_g2_points, "g2_monomial")?;
+
+        let is_legacy_format = tokens.clone().next().is_none();
+        let g1_monomial = if is_legacy_format {
+            derive_g1_monomial_from_lagrange(&g1_lagrange)?
+        } else {
+            take_txt_points(&mut tokens, num_g1_points, "g1_monomial")?
+        };
+
+        if tokens.next().is_some() {
+            return Err(TrustedSetupError::MalformedTxt {
+                reason: "unexpected trailing data after the G1 points in monomial form".to_string(),
+            });
+        }
+
+        let trusted_setup = TrustedSetup {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        };
+        trusted_setup.validate_trusted_setup()?;
+
+        Ok(trusted_setup)
+    }
+
+    /// Reads the file at `path` and parses it as a txt trusted setup.
+    ///
+    /// See `try_from_txt` for the expected format.
+    pub fn try_from_txt_file<P: AsRef<Path>>(path: P) -> Result<TrustedSetup, TrustedSetupError> {
+        let txt = std::fs::read_to_string(path)?;
+        Self::try_from_txt(&txt)
+    }
+
+    /// Serializes the trusted setup into the txt format used by c-kzg-4844.
+    ///
+    /// The output always contains the G1 points in monomial form, ie it is never in the legacy format.
+    pub fn to_txt(&self) -> String {
+        let points = self
+            .g1_lagrange
+            .iter()
+            .chain(&self.g2_monomial)
+            .chain(&self.g1_monomial)
+            .map(|point| point.strip_prefix("0x").unwrap_or(point));
+
+        let mut txt = format!("{}\n{}\n", self.g1_lagrange.len(), self.g2_monomial.len());
+        for point in points {
+            txt.push_str(point);
+            txt.push('\n');
+        }
+        txt
+    }
+
+    /// Serializes the trusted setup into the Json format specified by the ethereum trusted setup.
+    ///
+    /// See `from_json` for the format.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("serializing a TrustedSetup to json should not fail")
+    }
+
     /// Parse a Json string in the format specified by the ethereum trusted setup.
     ///
     /// This method does not check that the points are in the correct subgroup.
@@ -76,102 +325,441 @@ impl TrustedSetup {
             .expect("could not parse json string into a TrustedSetup structure")
     }
 
-    /// This validates that the points in the trusted setup are in the correct subgroup.
-    ///
-    /// Panics if any of the points are not in the correct subgroup
-    fn validate_trusted_setup(&self) {
-        self.to_commit_key(SubgroupCheck::Check);
-        self.to_opening_key(SubgroupCheck::Check);
+    /// This validates that the trusted setup has the expected number of points and
+    /// that all of the points are in the correct subgroup.
+    fn validate_trusted_setup(&self) -> Result<(), TrustedSetupError> {
+        check_num_points("g1_monomial", &self.g1_monomial, NUM_G1_POINTS)?;
+        check_num_points("g1_lagrange", &self.g1_lagrange, NUM_G1_POINTS)?;
+        check_num_points("g2_monomial", &self.g2_monomial, NUM_G2_POINTS)?;
+
+        deserialize_g1_points("g1_monomial", &self.g1_monomial, SubgroupCheck::Check)?;
+        deserialize_g1_points("g1_lagrange", &self.g1_lagrange, SubgroupCheck::Check)?;
+        deserialize_g2_points("g2_monomial", &self.g2_monomial, SubgroupCheck::Check)?;
+
+        Ok(())
     }
 
-    fn to_commit_key(&self, subgroup_check: SubgroupCheck) -> CommitKey {
-        let points = deserialize_g1_points(&self.g1_monomial, subgroup_check);
-        CommitKey::new(points)
+    fn to_commit_key(&self, subgroup_check: SubgroupCheck) -> Result<CommitKey, TrustedSetupError> {
+        let points = deserialize_g1_points("g1_monomial", &self.g1_monomial, subgroup_check)?;
+        Ok(CommitKey::new(points))
     }
 
-    fn to_opening_key(&self, subgroup_check: SubgroupCheck) -> OpeningKey {
-        let g2_points = deserialize_g2_points(&self.g2_monomial, subgroup_check);
-        let num_g2_points = g2_points.len();
+    /// Creates the opening key used to verify proofs for cells of `field_elements_per_cell` points.
+    pub(crate) fn opening_key(&self, field_elements_per_cell: usize) -> OpeningKey {
+        self.to_opening_key(SubgroupCheck::NoCheck, field_elements_per_cell)
+            .expect("trusted setup has malformed points")
+    }
+
+    fn to_opening_key(
+        &self,
+        subgroup_check: SubgroupCheck,
+        field_elements_per_cell: usize,
+    ) -> Result<OpeningKey, TrustedSetupError> {
+        let g2_points = deserialize_g2_points("g2_monomial", &self.g2_monomial, subgroup_check)?;
         // The setup needs as many g1 elements for the opening key as g2 elements, in order
         // to commit to the remainder/interpolation polynomial.
-        let g1_points = deserialize_g1_points(&self.g1_monomial[..num_g2_points], subgroup_check);
+        let num_g2_points = g2_points.len();
+        check_enough_g1_points(&self.g1_monomial, num_g2_points)?;
+        let g1_points = deserialize_g1_points(
+            "g1_monomial",
+            &self.g1_monomial[..num_g2_points],
+            subgroup_check,
+        )?;
 
-        OpeningKey::new(
+        Ok(OpeningKey::new(
             g1_points,
             g2_points,
-            FIELD_ELEMENTS_PER_CELL,
+            field_elements_per_cell,
             FIELD_ELEMENTS_PER_BLOB,
-        )
+        ))
     }
 
     /// Loads the official trusted setup file being used on mainnet from the embedded data folder.
+    ///
+    /// Note: If the `binary_trusted_setup` feature is enabled, this encodes the embedded points
+    /// as hex strings. Use `load_embedded` to avoid this when creating a context.
     fn from_embed() -> TrustedSetup {
-        Self::from_json_unchecked(TRUSTED_SETUP_JSON)
+        #[cfg(feature = "binary_trusted_setup")]
+        return load_embedded().to_trusted_setup();
+
+        #[cfg(not(feature = "binary_trusted_setup"))]
+        return load_embedded();
     }
 }
 
-/// Deserialize G1 points from hex strings without checking that the element
-/// is in the correct subgroup.
+/// The form of the trusted setup that is embedded in the library.
+#[cfg(feature = "binary_trusted_setup")]
+pub(crate) type EmbeddedTrustedSetup = DecodedTrustedSetup;
+/// The form of the trusted setup that is embedded in the library.
+#[cfg(not(feature = "binary_trusted_setup"))]
+pub(crate) type EmbeddedTrustedSetup = TrustedSetup;
+
+/// Loads the official trusted setup file being used on mainnet from the embedded data folder,
+/// without converting it to another form.
+///
+/// If the `binary_trusted_setup` feature is enabled, the binary encoding of the trusted setup
+/// is embedded instead of the Json encoding, which is faster to load.
+pub(crate) fn load_embedded() -> EmbeddedTrustedSetup {
+    #[cfg(feature = "binary_trusted_setup")]
+    return DecodedTrustedSetup::from_bytes(TRUSTED_SETUP_BYTES)
+        .expect("embedded binary trusted setup is malformed");
+
+    #[cfg(not(feature = "binary_trusted_setup"))]
+    return TrustedSetup::from_json_unchecked(TRUSTED_SETUP_JSON);
+}
+
+/// Checks that an array of points in the trusted setup has the expected number of points.
+fn check_num_points<T>(
+    field: &'static str,
+    points: &[T],
+    expected_num_points: usize,
+) -> Result<(), TrustedSetupError> {
+    if points.len() != expected_num_points {
+        return Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field,
+            num_points: points.len(),
+            expected_num_points,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that there are at least `num_points` G1 points in monomial form.
+fn check_enough_g1_points(
+    g1_monomial: &[String],
+    num_points: usize,
+) -> Result<(), TrustedSetupError> {
+    if g1_monomial.len() < num_points {
+        return Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field: "g1_monomial",
+            num_points: g1_monomial.len(),
+            expected_num_points: num_points,
+        });
+    }
+    Ok(())
+}
+
+/// Parses one of the counts at the start of a txt trusted setup.
+fn parse_txt_count(token: Option<&str>, what: &str) -> Result<usize, TrustedSetupError> {
+    let token = token.ok_or_else(|| TrustedSetupError::MalformedTxt {
+        reason: format!("missing the {what}"),
+    })?;
+    token.parse().map_err(|_| TrustedSetupError::MalformedTxt {
+        reason: format!("expected the {what}, found `{token}`"),
+    })
+}
+
+/// Takes `num_points` hex encoded points from a txt trusted setup.
+///
+/// The points are returned with a `0x` prefix, to match the Json format.
+fn take_txt_points<'a>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    num_points: usize,
+    field: &'static str,
+) -> Result<Vec<String>, TrustedSetupError> {
+    let points: Vec<_> = tokens
+        .take(num_points)
+        .map(|point| format!("0x{point}"))
+        .collect();
+
+    check_num_points(field, &points, num_points)?;
+
+    Ok(points)
+}
+
+/// Derives the G1 points in monomial form from the G1 points in lagrange form.
+///
+/// Note: Unlike blobs, the lagrange points in the trusted setup are stored in normal order.
+///
+/// Note: Since `[s^j]_1 = \sum_i \omega_i^j [L_i(s)]_1`, going from the lagrange points to
+/// the monomial points is a forward FFT over G1. (The inverse FFT is used for the opposite direction)
+fn derive_g1_monomial_from_lagrange<T: AsRef<str>>(
+    g1_lagrange: &[T],
+) -> Result<Vec<String>, TrustedSetupError> {
+    if !g1_lagrange.len().is_power_of_two() {
+        return Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field: "g1_lagrange",
+            num_points: g1_lagrange.len(),
+            expected_num_points: NUM_G1_POINTS,
+        });
+    }
+
+    let lagrange_points: Vec<_> =
+        deserialize_g1_points("g1_lagrange", g1_lagrange, SubgroupCheck::Check)?
+            .into_iter()
+            .map(G1Projective::from)
+            .collect();
+
+    let domain = Domain::new(lagrange_points.len());
+    let monomial_points = g1_batch_normalize(&domain.fft_g1(lagrange_points));
+
+    Ok(monomial_points
+        .iter()
+        .map(|point| format!("0x{}", hex::encode(point.to_compressed())))
+        .collect())
+}
+
+/// Decodes a `0x` prefixed hex string into a point encoding of `N` bytes.
+fn decode_hex_point<const N: usize>(
+    field: &'static str,
+    index: usize,
+    hex_str: &str,
+) -> Result<[u8; N], TrustedSetupError> {
+    let hex_str_without_0x = hex_str
+        .strip_prefix("0x")
+        .ok_or(TrustedSetupError::MalformedHex { field, index })?;
+    let point_bytes = hex::decode(hex_str_without_0x)
+        .map_err(|_| TrustedSetupError::MalformedHex { field, index })?;
+
+    let num_bytes = point_bytes.len();
+    point_bytes
+        .try_into()
+        .map_err(|_| TrustedSetupError::PointHasInvalidLength {
+            field,
+            index,
+            num_bytes,
+            expected_num_bytes: N,
+        })
+}
+
+/// Deserialize G1 points from hex strings, optionally checking that the points
+/// are in the correct subgroup.
 fn deserialize_g1_points<T: AsRef<str>>(
+    field: &'static str,
     g1_points_hex_str: &[T],
     check: SubgroupCheck,
-) -> Vec<G1Point> {
-    let mut g1_points = Vec::new();
-    for g1_hex_str in g1_points_hex_str {
-        let g1_hex_str = g1_hex_str.as_ref();
-
-        let g1_hex_str_without_0x = g1_hex_str
-            .strip_prefix("0x")
-            .expect("expected hex points to be prefixed with `0x`");
-        let g1_point_bytes: [u8; 48] = hex::decode(g1_hex_str_without_0x)
-            .expect("trusted setup has malformed g1 points")
-            .try_into()
-            .expect("expected 48 bytes for G1 point");
+) -> Result<Vec<G1Point>, TrustedSetupError> {
+    let mut g1_points = Vec::with_capacity(g1_points_hex_str.len());
+    for (index, g1_hex_str) in g1_points_hex_str.iter().enumerate() {
+        let g1_point_bytes: [u8; 48] = decode_hex_point(field, index, g1_hex_str.as_ref())?;
 
-        let point = match check {
-            SubgroupCheck::Check => {
-                G1Point::from_compressed(&g1_point_bytes).expect("invalid g1 point")
-            }
-            SubgroupCheck::NoCheck => {
-                G1Point::from_compressed_unchecked(&g1_point_bytes).expect("invalid g1 point")
-            }
+        let point: Option<G1Point> = match check {
+            SubgroupCheck::Check => G1Point::from_compressed(&g1_point_bytes).into(),
+            SubgroupCheck::NoCheck => G1Point::from_compressed_unchecked(&g1_point_bytes).into(),
         };
+        let point = point.ok_or_else(|| {
+            // Distinguish between points that are not on the curve and points that are
+            // not in the correct subgroup.
+            if G1Point::from_compressed_unchecked(&g1_point_bytes)
+                .is_some()
+                .into()
+            {
+                TrustedSetupError::PointNotInSubgroup { field, index }
+            } else {
+                TrustedSetupError::PointNotOnCurve { field, index }
+            }
+        })?;
 
         g1_points.push(point)
     }
 
-    g1_points
+    Ok(g1_points)
 }
 
-/// Deserialize G2 points from hex strings without checking that the element
-/// is in the correct subgroup.
+/// Deserialize G2 points from hex strings, optionally checking that the points
+/// are in the correct subgroup.
 fn deserialize_g2_points<T: AsRef<str>>(
+    field: &'static str,
     g2_points_hex_str: &[T],
     subgroup_check: SubgroupCheck,
-) -> Vec<G2Point> {
-    let mut g2_points = Vec::new();
-    for g2_hex_str in g2_points_hex_str {
-        let g2_hex_str = g2_hex_str.as_ref();
-        let g2_hex_str_without_0x = g2_hex_str
-            .strip_prefix("0x")
-            .expect("expected hex points to be prefixed with `0x`");
-        let g2_point_bytes: [u8; 96] = hex::decode(g2_hex_str_without_0x)
-            .expect("trusted setup has malformed g2 points")
-            .try_into()
-            .expect("expected 96 bytes for G2 point");
-
-        let point = match subgroup_check {
-            SubgroupCheck::Check => G2Point::from_compressed(&g2_point_bytes).unwrap(),
-            SubgroupCheck::NoCheck => G2Point::from_compressed_unchecked(&g2_point_bytes).unwrap(),
+) -> Result<Vec<G2Point>, TrustedSetupError> {
+    let mut g2_points = Vec::with_capacity(g2_points_hex_str.len());
+    for (index, g2_hex_str) in g2_points_hex_str.iter().enumerate() {
+        let g2_point_bytes: [u8; 96] = decode_hex_point(field, index, g2_hex_str.as_ref())?;
+
+        let point: Option<G2Point> = match subgroup_check {
+            SubgroupCheck::Check => G2Point::from_compressed(&g2_point_bytes).into(),
+            SubgroupCheck::NoCheck => G2Point::from_compressed_unchecked(&g2_point_bytes).into(),
         };
+        let point = point.ok_or_else(|| {
+            // Distinguish between points that are not on the curve and points that are
+            // not in the correct subgroup.
+            if G2Point::from_compressed_unchecked(&g2_point_bytes)
+                .is_some()
+                .into()
+            {
+                TrustedSetupError::PointNotInSubgroup { field, index }
+            } else {
+                TrustedSetupError::PointNotOnCurve { field, index }
+            }
+        })?;
+
         g2_points.push(point)
     }
 
-    g2_points
+    Ok(g2_points)
 }
 
 #[test]
 fn test_embedded_setup_has_points_in_correct_subgroup() {
     let setup = TrustedSetup::default();
-    setup.validate_trusted_setup();
+    setup.validate_trusted_setup().unwrap();
+}
+
+#[test]
+fn test_try_from_json_accepts_embedded_setup() {
+    let setup = TrustedSetup::try_from_json(TRUSTED_SETUP_JSON).unwrap();
+    assert_eq!(setup, TrustedSetup::default());
+}
+
+#[test]
+fn test_try_from_json_reports_malformed_setups() {
+    fn try_from_setup(setup: &TrustedSetup) -> Result<TrustedSetup, TrustedSetupError> {
+        TrustedSetup::try_from_json(&serde_json::to_string(setup).unwrap())
+    }
+
+    assert!(matches!(
+        TrustedSetup::try_from_json("{ not json"),
+        Err(TrustedSetupError::MalformedJson { .. })
+    ));
+    assert!(matches!(
+        TrustedSetup::try_from_json(r#"{"g1_monomial": [], "g2_monomial": []}"#),
+        Err(TrustedSetupError::MissingField {
+            field: "g1_lagrange"
+        })
+    ));
+
+    let mut setup = TrustedSetup::default();
+    setup.g2_monomial.pop();
+    assert!(matches!(
+        try_from_setup(&setup),
+        Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field: "g2_monomial",
+            num_points: 64,
+            expected_num_points: 65,
+        })
+    ));
+
+    let mut setup = TrustedSetup::default();
+    setup.g1_lagrange[3] = setup.g1_lagrange[3].replacen("0x", "", 1);
+    assert!(matches!(
+        try_from_setup(&setup),
+        Err(TrustedSetupError::MalformedHex {
+            field: "g1_lagrange",
+            index: 3
+        })
+    ));
+
+    let mut setup = TrustedSetup::default();
+    setup.g1_monomial[7].push_str("00");
+    assert!(matches!(
+        try_from_setup(&setup),
+        Err(TrustedSetupError::PointHasInvalidLength {
+            field: "g1_monomial",
+            index: 7,
+            num_bytes: 49,
+            expected_num_bytes: 48,
+        })
+    ));
+
+    // Modify the x coordinate of a point until it is no longer on the curve,
+    // and until it is on the curve, but not in the subgroup.
+    let mut not_on_curve = None;
+    let mut not_in_subgroup = None;
+    let mut point_bytes: [u8; 48] = hex::decode(&TrustedSetup::default().g1_monomial[1][2..])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    while not_on_curve.is_none() || not_in_subgroup.is_none() {
+        point_bytes[47] = point_bytes[47].wrapping_add(1);
+        let is_on_curve: bool = G1Point::from_compressed_unchecked(&point_bytes)
+            .is_some()
+            .into();
+        let point_hex = format!("0x{}", hex::encode(point_bytes));
+        if is_on_curve {
+            not_in_subgroup.get_or_insert(point_hex);
+        } else {
+            not_on_curve.get_or_insert(point_hex);
+        }
+    }
+
+    let mut setup = TrustedSetup::default();
+    setup.g1_monomial[1] = not_on_curve.unwrap();
+    assert!(matches!(
+        try_from_setup(&setup),
+        Err(TrustedSetupError::PointNotOnCurve {
+            field: "g1_monomial",
+            index: 1
+        })
+    ));
+
+    let mut setup = TrustedSetup::default();
+    setup.g1_monomial[1] = not_in_subgroup.unwrap();
+    assert!(matches!(
+        try_from_setup(&setup),
+        Err(TrustedSetupError::PointNotInSubgroup {
+            field: "g1_monomial",
+            index: 1
+        })
+    ));
+}
+
+#[test]
+fn test_try_from_file_reports_missing_file() {
+    assert!(matches!(
+        TrustedSetup::try_from_file("this/file/does/not/exist.json"),
+        Err(TrustedSetupError::Io(_))
+    ));
+}
+
+#[test]
+fn test_txt_round_trip() {
+    let setup = TrustedSetup::default();
+
+    let txt = setup.to_txt();
+    let setup_from_txt = TrustedSetup::try_from_txt(&txt).unwrap();
+    assert_eq!(setup_from_txt, setup);
+
+    let setup_from_json = TrustedSetup::try_from_json(&setup_from_txt.to_json()).unwrap();
+    assert_eq!(setup_from_json, setup);
+}
+
+#[test]
+fn test_legacy_txt_derives_g1_monomial_points() {
+    let setup = TrustedSetup::default();
+
+    // The legacy format does not contain the G1 points in monomial form
+    let txt = setup.to_txt();
+    let num_lines_without_g1_monomial = 2 + setup.g1_lagrange.len() + setup.g2_monomial.len();
+    let legacy_txt: String = txt
+        .lines()
+        .take(num_lines_without_g1_monomial)
+        .map(|line| format!("{line}\n"))
+        .collect();
+
+    let setup_from_legacy_txt = TrustedSetup::try_from_txt(&legacy_txt).unwrap();
+    assert_eq!(setup_from_legacy_txt, setup);
+}
+
+#[test]
+fn test_try_from_txt_reports_malformed_setups() {
+    assert!(matches!(
+        TrustedSetup::try_from_txt(""),
+        Err(TrustedSetupError::MalformedTxt { .. })
+    ));
+    assert!(matches!(
+        TrustedSetup::try_from_txt("4096\nsixty-five\n"),
+        Err(TrustedSetupError::MalformedTxt { .. })
+    ));
+
+    let setup = TrustedSetup::default();
+    let mut lines: Vec<_> = setup.to_txt().lines().map(str::to_string).collect();
+
+    // Truncated G2 points
+    let truncated_txt = lines[..2 + setup.g1_lagrange.len() + 10].join("\n");
+    assert!(matches!(
+        TrustedSetup::try_from_txt(&truncated_txt),
+        Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field: "g2_monomial",
+            num_points: 10,
+            expected_num_points: 65,
+        })
+    ));
+
+    // Trailing data
+    lines.push("00".to_string());
+    assert!(matches!(
+        TrustedSetup::try_from_txt(&lines.join("\n")),
+        Err(TrustedSetupError::MalformedTxt { .. })
+    ));
 }
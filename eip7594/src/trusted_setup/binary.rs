@@ -0,0 +1,378 @@
+//! A versioned binary encoding of the trusted setup.
+//!
+//! Parsing the Json trusted setup requires decoding thousands of hex strings and
+//! decompressing each point, which requires a square root. The binary encoding stores
+//! the points in uncompressed affine form, so that they can be loaded without either.
+//!
+// The layout is the following, where all integers are big-endian:
+/*
+    magic               8 bytes             `ETHKZGTS`
+    version             4 bytes
+    num_g1_points       4 bytes
+    num_g2_points       4 bytes
+    g1_monomial         num_g1_points * 96 bytes
+    g1_lagrange         num_g1_points * 96 bytes
+    g2_monomial         num_g2_points * 192 bytes
+    checksum            32 bytes            sha256 of all of the preceding bytes
+*/
+
+use bls12_381::{G1Point, G2Point};
+use kzg_multi_open::{commit_key::CommitKey, opening_key::OpeningKey};
+use sha2::{Digest, Sha256};
+
+use super::{
+    check_num_points, deserialize_g1_points, deserialize_g2_points, SubgroupCheck, TrustedSetup,
+    NUM_G1_POINTS, NUM_G2_POINTS,
+};
+use crate::{
+    constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL},
+    errors::TrustedSetupError,
+};
+
+/// Magic bytes at the start of a binary trusted setup.
+const MAGIC: &[u8; 8] = b"ETHKZGTS";
+/// The version of the binary encoding that this module reads and writes.
+const VERSION: u32 = 1;
+/// The size of the header: magic, version and the number of G1 and G2 points.
+const HEADER_SIZE: usize = MAGIC.len() + 3 * 4;
+/// The size of the sha256 checksum at the end of the encoding.
+const CHECKSUM_SIZE: usize = 32;
+
+/// A trusted setup whose points have already been decoded.
+///
+/// This is what a binary trusted setup is loaded into, so that creating a context from it
+/// does not need to decode any hex strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedTrustedSetup {
+    pub g1_monomial: Vec<G1Point>,
+    pub g1_lagrange: Vec<G1Point>,
+    pub g2_monomial: Vec<G2Point>,
+}
+
+impl From<&DecodedTrustedSetup> for CommitKey {
+    fn from(setup: &DecodedTrustedSetup) -> Self {
+        CommitKey::new(setup.g1_monomial.clone())
+    }
+}
+
+impl From<&DecodedTrustedSetup> for OpeningKey {
+    fn from(setup: &DecodedTrustedSetup) -> Self {
+        setup.opening_key(FIELD_ELEMENTS_PER_CELL)
+    }
+}
+
+impl TrustedSetup {
+    /// Serializes the trusted setup into its binary encoding.
+    ///
+    /// Panics if the trusted setup contains malformed points.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        DecodedTrustedSetup::from(self).to_bytes()
+    }
+
+    /// Deserializes a trusted setup from its binary encoding.
+    ///
+    /// Note: This encodes every point as a hex string. Use `DecodedTrustedSetup::from_bytes`
+    /// to create a context from the binary encoding without doing so.
+    ///
+    /// See `DecodedTrustedSetup::from_bytes` for more details.
+    pub fn from_bytes(bytes: &[u8]) -> Result<TrustedSetup, TrustedSetupError> {
+        DecodedTrustedSetup::from_bytes(bytes).map(|setup| setup.to_trusted_setup())
+    }
+}
+
+// Note: This conversion panics if the trusted setup contains malformed points.
+impl From<&TrustedSetup> for DecodedTrustedSetup {
+    fn from(setup: &TrustedSetup) -> Self {
+        DecodedTrustedSetup {
+            g1_monomial: deserialize_g1_points(
+                "g1_monomial",
+                &setup.g1_monomial,
+                SubgroupCheck::NoCheck,
+            )
+            .expect("trusted setup has malformed g1 monomial points"),
+            g1_lagrange: deserialize_g1_points(
+                "g1_lagrange",
+                &setup.g1_lagrange,
+                SubgroupCheck::NoCheck,
+            )
+            .expect("trusted setup has malformed g1 lagrange points"),
+            g2_monomial: deserialize_g2_points(
+                "g2_monomial",
+                &setup.g2_monomial,
+                SubgroupCheck::NoCheck,
+            )
+            .expect("trusted setup has malformed g2 monomial points"),
+        }
+    }
+}
+
+impl DecodedTrustedSetup {
+    /// Encodes the points as hex strings, in the format used by the Json trusted setup.
+    pub fn to_trusted_setup(&self) -> TrustedSetup {
+        TrustedSetup {
+            g1_monomial: self
+                .g1_monomial
+                .iter()
+                .map(|p| to_hex(p.to_compressed()))
+                .collect(),
+            g1_lagrange: self
+                .g1_lagrange
+                .iter()
+                .map(|p| to_hex(p.to_compressed()))
+                .collect(),
+            g2_monomial: self
+                .g2_monomial
+                .iter()
+                .map(|p| to_hex(p.to_compressed()))
+                .collect(),
+        }
+    }
+
+    /// Creates the opening key used to verify proofs for cells of `field_elements_per_cell` points.
+    ///
+    /// Panics if there are fewer G1 points than G2 points.
+    pub(crate) fn opening_key(&self, field_elements_per_cell: usize) -> OpeningKey {
+        // The setup needs as many g1 elements for the opening key as g2 elements, in order
+        // to commit to the remainder/interpolation polynomial.
+        let num_g2_points = self.g2_monomial.len();
+        assert!(
+            self.g1_monomial.len() >= num_g2_points,
+            "trusted setup has fewer g1 points than g2 points"
+        );
+
+        OpeningKey::new(
+            self.g1_monomial[..num_g2_points].to_vec(),
+            self.g2_monomial.clone(),
+            field_elements_per_cell,
+            FIELD_ELEMENTS_PER_BLOB,
+        )
+    }
+
+    /// Serializes the trusted setup into its binary encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let DecodedTrustedSetup {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        } = self;
+
+        let mut bytes = Vec::with_capacity(
+            HEADER_SIZE
+                + (g1_monomial.len() + g1_lagrange.len()) * G1Point::uncompressed_size()
+                + g2_monomial.len() * G2Point::uncompressed_size()
+                + CHECKSUM_SIZE,
+        );
+
+        bytes.extend(MAGIC);
+        bytes.extend(VERSION.to_be_bytes());
+        bytes.extend((g1_monomial.len() as u32).to_be_bytes());
+        bytes.extend((g2_monomial.len() as u32).to_be_bytes());
+        for point in g1_monomial.iter().chain(g1_lagrange) {
+            bytes.extend(point.to_uncompressed());
+        }
+        for point in g2_monomial {
+            bytes.extend(point.to_uncompressed());
+        }
+
+        let checksum: [u8; CHECKSUM_SIZE] = Sha256::digest(&bytes).into();
+        bytes.extend(checksum);
+
+        bytes
+    }
+
+    /// Deserializes a trusted setup from its binary encoding.
+    ///
+    /// Note: This method checks that the points are on the curve, but does not check that
+    /// they are in the correct subgroup. The checksum only protects against accidental corruption,
+    /// so this method should only be used with bytes that were produced by `to_bytes` from a
+    /// trusted setup that was validated.
+    pub fn from_bytes(bytes: &[u8]) -> Result<DecodedTrustedSetup, TrustedSetupError> {
+        if bytes.len() < HEADER_SIZE + CHECKSUM_SIZE {
+            return Err(TrustedSetupError::MalformedBytes {
+                reason: format!("expected at least {} bytes", HEADER_SIZE + CHECKSUM_SIZE),
+            });
+        }
+
+        let (payload, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+        let (header, points) = payload.split_at(HEADER_SIZE);
+
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(TrustedSetupError::MalformedBytes {
+                reason: "expected the binary trusted setup to start with the magic bytes"
+                    .to_string(),
+            });
+        }
+        let version = read_u32(&header[MAGIC.len()..]);
+        if version != VERSION {
+            return Err(TrustedSetupError::UnsupportedVersion { version });
+        }
+        let num_g1_points = read_u32(&header[MAGIC.len() + 4..]) as usize;
+        let num_g2_points = read_u32(&header[MAGIC.len() + 8..]) as usize;
+
+        // The sizes are computed with checked arithmetic, since the number of points comes
+        // from untrusted bytes and could overflow a 32-bit `usize`.
+        let g1_points_size = num_g1_points.checked_mul(G1Point::uncompressed_size());
+        let g2_points_size = num_g2_points.checked_mul(G2Point::uncompressed_size());
+        let expected_points_size = g1_points_size
+            .and_then(|size| size.checked_mul(2))
+            .zip(g2_points_size)
+            .and_then(|(g1_size, g2_size)| g1_size.checked_add(g2_size));
+        if expected_points_size != Some(points.len()) {
+            return Err(TrustedSetupError::MalformedBytes {
+                reason: format!(
+                    "expected {num_g1_points} g1 points and {num_g2_points} g2 points, found {} bytes of points",
+                    points.len()
+                ),
+            });
+        }
+
+        let expected_checksum: [u8; CHECKSUM_SIZE] = Sha256::digest(payload).into();
+        if checksum != expected_checksum {
+            return Err(TrustedSetupError::ChecksumMismatch);
+        }
+
+        let (g1_points, g2_points) =
+            points.split_at(2 * num_g1_points * G1Point::uncompressed_size());
+        let (g1_monomial_bytes, g1_lagrange_bytes) =
+            g1_points.split_at(num_g1_points * G1Point::uncompressed_size());
+
+        let g1_monomial = deserialize_uncompressed_g1_points("g1_monomial", g1_monomial_bytes)?;
+        let g1_lagrange = deserialize_uncompressed_g1_points("g1_lagrange", g1_lagrange_bytes)?;
+        let g2_monomial = deserialize_uncompressed_g2_points("g2_monomial", g2_points)?;
+
+        check_num_points("g1_monomial", &g1_monomial, NUM_G1_POINTS)?;
+        check_num_points("g1_lagrange", &g1_lagrange, NUM_G1_POINTS)?;
+        check_num_points("g2_monomial", &g2_monomial, NUM_G2_POINTS)?;
+
+        Ok(DecodedTrustedSetup {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().expect("slice has at least 4 bytes"))
+}
+
+fn to_hex<T: AsRef<[u8]>>(bytes: T) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn deserialize_uncompressed_g1_points(
+    field: &'static str,
+    bytes: &[u8],
+) -> Result<Vec<G1Point>, TrustedSetupError> {
+    bytes
+        .chunks_exact(G1Point::uncompressed_size())
+        .enumerate()
+        .map(|(index, point_bytes)| {
+            let point_bytes = point_bytes.try_into().expect("chunk has the correct size");
+            Option::from(G1Point::from_uncompressed_unchecked(point_bytes))
+                .ok_or(TrustedSetupError::PointNotOnCurve { field, index })
+        })
+        .collect()
+}
+
+fn deserialize_uncompressed_g2_points(
+    field: &'static str,
+    bytes: &[u8],
+) -> Result<Vec<G2Point>, TrustedSetupError> {
+    bytes
+        .chunks_exact(G2Point::uncompressed_size())
+        .enumerate()
+        .map(|(index, point_bytes)| {
+            let point_bytes = point_bytes.try_into().expect("chunk has the correct size");
+            Option::from(G2Point::from_uncompressed_unchecked(point_bytes))
+                .ok_or(TrustedSetupError::PointNotOnCurve { field, index })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::{TrustedSetupRef, TRUSTED_SETUP_BYTES, TRUSTED_SETUP_JSON};
+
+    #[test]
+    fn binary_round_trip() {
+        let setup = TrustedSetup::from_json(TRUSTED_SETUP_JSON);
+
+        let bytes = setup.to_bytes();
+        assert_eq!(TrustedSetup::from_bytes(&bytes).unwrap(), setup);
+
+        let setup_from_bytes = DecodedTrustedSetup::from_bytes(&bytes).unwrap();
+        assert_eq!(setup_from_bytes.to_trusted_setup(), setup);
+        assert_eq!(setup_from_bytes.to_bytes(), bytes);
+
+        // The keys and digest created from the decoded points should be the same
+        // as the ones created from the hex encoded points.
+        assert_eq!(
+            CommitKey::from(&setup_from_bytes).g1s,
+            CommitKey::from(&setup).g1s
+        );
+        let opening_key_from_bytes = OpeningKey::from(&setup_from_bytes);
+        let opening_key = OpeningKey::from(&setup);
+        assert_eq!(opening_key_from_bytes.g1s, opening_key.g1s);
+        assert_eq!(opening_key_from_bytes.g2s, opening_key.g2s);
+        assert_eq!(
+            TrustedSetupRef::from(&setup_from_bytes).digest(),
+            TrustedSetupRef::from(&setup).digest()
+        );
+    }
+
+    #[test]
+    fn embedded_binary_setup_matches_json_setup() {
+        // If this test fails, the embedded binary trusted setup can be regenerated
+        // by writing `TrustedSetup::from_json(..).to_bytes()` to `data/trusted_setup_4096.bin`.
+        let setup = TrustedSetup::from_json(TRUSTED_SETUP_JSON);
+        assert_eq!(setup.to_bytes(), TRUSTED_SETUP_BYTES);
+    }
+
+    #[test]
+    fn from_bytes_reports_malformed_bytes() {
+        let bytes = TRUSTED_SETUP_BYTES.to_vec();
+
+        assert!(matches!(
+            TrustedSetup::from_bytes(&bytes[..10]),
+            Err(TrustedSetupError::MalformedBytes { .. })
+        ));
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(
+            TrustedSetup::from_bytes(&wrong_magic),
+            Err(TrustedSetupError::MalformedBytes { .. })
+        ));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[MAGIC.len() + 3] = 2;
+        assert!(matches!(
+            TrustedSetup::from_bytes(&wrong_version),
+            Err(TrustedSetupError::UnsupportedVersion { version: 2 })
+        ));
+
+        let mut truncated = bytes.clone();
+        truncated.drain(HEADER_SIZE..HEADER_SIZE + 96);
+        assert!(matches!(
+            TrustedSetup::from_bytes(&truncated),
+            Err(TrustedSetupError::MalformedBytes { .. })
+        ));
+
+        // A header with a number of points whose size overflows should not panic
+        let mut too_many_points = bytes.clone();
+        too_many_points[MAGIC.len() + 4..MAGIC.len() + 12].fill(0xff);
+        assert!(matches!(
+            TrustedSetup::from_bytes(&too_many_points),
+            Err(TrustedSetupError::MalformedBytes { .. })
+        ));
+
+        let mut corrupted = bytes;
+        corrupted[HEADER_SIZE + 100] ^= 1;
+        assert!(matches!(
+            TrustedSetup::from_bytes(&corrupted),
+            Err(TrustedSetupError::ChecksumMismatch)
+        ));
+    }
+}
@@ -1,61 +1,155 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 pub use crate::errors::VerifierError;
 
 use crate::{
-    constants::{
-        CELLS_PER_EXT_BLOB, EXTENSION_FACTOR, FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_EXT_BLOB,
+    config::DASConfig,
+    constants::FIELD_ELEMENTS_PER_BLOB,
+    errors::{ConfigError, Error},
+    serialization::{
+        deserialize_blob_to_scalars, deserialize_cell_to_scalars, deserialize_cells,
+        deserialize_compressed_g1, deserialize_compressed_g1_points, deserialize_scalar,
     },
-    errors::Error,
-    serialization::{deserialize_cells, deserialize_compressed_g1_points},
-    trusted_setup::TrustedSetup,
-    Bytes48Ref, CellIndex, CellRef, DASContext,
+    trusted_setup::{load_embedded, TrustedSetupRef},
+    BlobRef, Bytes32Ref, Bytes48Ref, CellIndex, CellRef, DASContext, KZGCommitment, RowIndex,
 };
-use bls12_381::Scalar;
+use bls12_381::{reduce_bytes_to_scalar, G1Point, Scalar};
 use erasure_codes::{BlockErasureIndices, ReedSolomon};
 use kzg_multi_open::{
-    opening_key::OpeningKey,
-    {Prover, Verifier},
+    single_point::SinglePointVerifier,
+    {Prover, ProverInput, Verifier},
 };
+use rand::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
 
 /// The context object that is used to call functions in the verifier API.
 #[derive(Debug)]
 pub struct VerifierContext {
-    kzg_multipoint_verifier: Verifier,
-    rs: ReedSolomon,
+    pub(crate) kzg_multipoint_verifier: Verifier,
+    kzg_single_point_verifier: SinglePointVerifier,
+    pub(crate) rs: ReedSolomon,
+    pub(crate) config: DASConfig,
+    /// Digest of the trusted setup that this context was created from.
+    ///
+    /// This is used to check that batches of cells are verified against the same trusted setup.
+    pub(crate) trusted_setup_digest: [u8; 32],
 }
 
 impl Default for VerifierContext {
     fn default() -> Self {
-        let trusted_setup = TrustedSetup::default();
-        Self::new(&trusted_setup)
+        let trusted_setup = load_embedded();
+        Self::new(&trusted_setup, DASConfig::default())
+            .expect("the embedded trusted setup supports the mainnet config")
     }
 }
 
 impl VerifierContext {
-    pub fn new(trusted_setup: &TrustedSetup) -> VerifierContext {
-        let opening_key = OpeningKey::from(trusted_setup);
+    /// Creates a new verifier context for the given config.
+    ///
+    /// Returns an error if the trusted setup does not contain enough points for the config.
+    pub fn new<'a>(
+        trusted_setup: impl Into<TrustedSetupRef<'a>>,
+        config: DASConfig,
+    ) -> Result<VerifierContext, ConfigError> {
+        let trusted_setup = trusted_setup.into();
+        config.validate(trusted_setup)?;
+
+        let opening_key = trusted_setup.opening_key(config.field_elements_per_cell());
 
-        let multipoint_verifier =
-            Verifier::new(opening_key, FIELD_ELEMENTS_PER_EXT_BLOB, CELLS_PER_EXT_BLOB);
+        let multipoint_verifier = Verifier::new(
+            opening_key.clone(),
+            config.field_elements_per_ext_blob(),
+            config.cells_per_ext_blob(),
+        );
+        let single_point_verifier = SinglePointVerifier::new(opening_key, FIELD_ELEMENTS_PER_BLOB);
 
-        VerifierContext {
+        Ok(VerifierContext {
             rs: ReedSolomon::new(
-                FIELD_ELEMENTS_PER_BLOB,
-                EXTENSION_FACTOR,
-                CELLS_PER_EXT_BLOB,
+                config.field_elements_per_blob(),
+                config.extension_factor(),
+                config.cells_per_ext_blob(),
             ),
             kzg_multipoint_verifier: multipoint_verifier,
-        }
+            kzg_single_point_verifier: single_point_verifier,
+            config,
+            trusted_setup_digest: trusted_setup.digest(),
+        })
+    }
+
+    /// Returns the config that this context was created with.
+    pub fn config(&self) -> &DASConfig {
+        &self.config
+    }
+}
+
+/// A commitment that has already been deserialized and checked to be a valid point.
+///
+/// Validating a commitment is relatively expensive, since it involves decompressing the point
+/// and checking that it is in the correct subgroup. When the same commitments are used in many
+/// calls, such as when verifying every column of a block, the commitments can be validated once
+/// and then passed to `verify_cell_kzg_proof_batch_with_validated_commitments`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidatedCommitment {
+    bytes: KZGCommitment,
+    point: G1Point,
+}
+
+// The point is determined by the bytes, so we only hash the bytes.
+impl std::hash::Hash for ValidatedCommitment {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
     }
 }
 
-fn find_missing_cell_indices(present_cell_indices: &[usize]) -> Vec<usize> {
+impl ValidatedCommitment {
+    /// Deserializes and validates a commitment.
+    ///
+    /// Returns an error if the bytes do not represent a valid commitment.
+    pub fn new(commitment: Bytes48Ref) -> Result<Self, Error> {
+        let point = deserialize_compressed_g1(commitment)?;
+        Ok(ValidatedCommitment {
+            bytes: *commitment,
+            point,
+        })
+    }
+
+    /// Returns the serialized form of the commitment.
+    pub fn to_bytes(&self) -> KZGCommitment {
+        self.bytes
+    }
+}
+
+/// Computes the challenge point at which a blob is opened, in order to check that the blob
+/// is consistent with its commitment.
+///
+/// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#compute_challenge
+pub(crate) fn compute_challenge(blob: BlobRef, commitment: Bytes48Ref) -> Scalar {
+    const DOMAIN_SEP: &str = "FSBLOBVERIFY_V1_";
+
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN_SEP);
+    // The degree of the polynomial is appended as a domain separator
+    hasher.update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+    hasher.update(blob);
+    hasher.update(commitment);
+    let result: [u8; 32] = hasher.finalize().into();
+
+    reduce_bytes_to_scalar(result)
+}
+
+fn find_missing_cell_indices(
+    present_cell_indices: &[usize],
+    cells_per_ext_blob: usize,
+) -> Vec<usize> {
     let cell_indices: HashSet<_> = present_cell_indices.iter().cloned().collect();
 
     let mut missing = Vec::new();
 
-    for i in 0..CELLS_PER_EXT_BLOB {
+    for i in 0..cells_per_ext_blob {
         if !cell_indices.contains(&i) {
             missing.push(i);
         }
@@ -91,7 +185,166 @@ fn deduplicate_with_indices<T: Eq + std::hash::Hash + Clone>(input: Vec<T>) -> (
     (unique_items, indices)
 }
 
+/// Returns the positions in `range` of the items that are invalid, given a function
+/// that checks whether a contiguous range of items is valid as a batch.
+///
+/// If a batch is invalid, it is split in half and each half is checked on its own,
+/// until the invalid items are found. This means that a batch with no invalid items
+/// is only checked once, and each invalid item costs a logarithmic number of checks.
+///
+/// The positions are returned in ascending order.
+fn find_invalid_items<F>(range: Range<usize>, is_valid: &F) -> Vec<usize>
+where
+    F: Fn(Range<usize>) -> bool + Sync,
+{
+    if range.is_empty() || is_valid(range.clone()) {
+        return Vec::new();
+    }
+    if range.len() == 1 {
+        return vec![range.start];
+    }
+
+    let mid = range.start + range.len() / 2;
+    let (mut invalid_items, invalid_items_right) = rayon::join(
+        || find_invalid_items(range.start..mid, is_valid),
+        || find_invalid_items(mid..range.end, is_valid),
+    );
+    invalid_items.extend(invalid_items_right);
+    invalid_items
+}
+
 impl DASContext {
+    /// Verifies a KZG proof attesting that the polynomial committed to by `commitment`
+    /// evaluates to `y` at the point `z`.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#verify_kzg_proof
+    pub fn verify_kzg_proof(
+        &self,
+        commitment: Bytes48Ref,
+        z: Bytes32Ref,
+        y: Bytes32Ref,
+        proof: Bytes48Ref,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            // Deserialization
+            //
+            let commitment = deserialize_compressed_g1(commitment)?;
+            let z = deserialize_scalar(z)?;
+            let y = deserialize_scalar(y)?;
+            let proof = deserialize_compressed_g1(proof)?;
+
+            // Computation
+            //
+            let ok = self
+                .verifier_ctx
+                .kzg_single_point_verifier
+                .verify(commitment, z, y, proof);
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
+    /// Verifies a KZG proof that attests to the blob being consistent with its commitment.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#verify_blob_kzg_proof
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: BlobRef,
+        commitment: Bytes48Ref,
+        proof: Bytes48Ref,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            // Deserialization
+            //
+            let scalars = deserialize_blob_to_scalars(blob)?;
+            let commitment_ = deserialize_compressed_g1(commitment)?;
+            let proof = deserialize_compressed_g1(proof)?;
+
+            // Computation
+            //
+            let z = compute_challenge(blob, commitment);
+            let y = self
+                .verifier_ctx
+                .kzg_single_point_verifier
+                .evaluate(ProverInput::Data(scalars), z);
+            let ok = self
+                .verifier_ctx
+                .kzg_single_point_verifier
+                .verify(commitment_, z, y, proof);
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
+    /// Verifies a batch of KZG proofs, where each proof attests to a blob being consistent
+    /// with its commitment.
+    ///
+    /// This is faster than calling `verify_blob_kzg_proof` for each blob.
+    ///
+    /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md#verify_blob_kzg_proof_batch
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: Vec<BlobRef>,
+        commitments: Vec<Bytes48Ref>,
+        proofs: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            // Validation
+            //
+            validation::verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)?;
+
+            // If there are no inputs, we return early with no error
+            //
+            if blobs.is_empty() {
+                return Ok(());
+            }
+
+            // Deserialization
+            //
+            let commitments_ = deserialize_compressed_g1_points(commitments.clone())?;
+            let proofs_ = deserialize_compressed_g1_points(proofs)?;
+
+            // Computation
+            //
+            let mut zs = Vec::with_capacity(blobs.len());
+            let mut ys = Vec::with_capacity(blobs.len());
+            for (blob, commitment) in blobs.into_iter().zip(commitments) {
+                let scalars = deserialize_blob_to_scalars(blob)?;
+                let z = compute_challenge(blob, commitment);
+                ys.push(
+                    self.verifier_ctx
+                        .kzg_single_point_verifier
+                        .evaluate(ProverInput::Data(scalars), z),
+                );
+                zs.push(z);
+            }
+
+            let ok = self.verifier_ctx.kzg_single_point_verifier.verify_batch(
+                &commitments_,
+                &zs,
+                &ys,
+                &proofs_,
+            );
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
     /// Given a collection of commitments, cells and proofs, this functions verifies that
     /// the cells are consistent with the commitments using their respective KZG proofs.
     pub fn verify_cell_kzg_proof_batch(
@@ -109,6 +362,7 @@ impl DASContext {
             // Validation
             //
             validation::verify_cell_kzg_proof_batch(
+                &self.verifier_ctx.config,
                 &deduplicated_commitments,
                 &row_indices,
                 &cell_indices,
@@ -150,6 +404,295 @@ impl DASContext {
         })
     }
 
+    /// Verifies a batch of cells in the same way as `verify_cell_kzg_proof_batch`, but using
+    /// commitments that have already been validated.
+    ///
+    /// This avoids deserializing the same commitments again on every call.
+    pub fn verify_cell_kzg_proof_batch_with_validated_commitments(
+        &self,
+        commitments: Vec<&ValidatedCommitment>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+            // Validation
+            //
+            validation::verify_cell_kzg_proof_batch(
+                &self.verifier_ctx.config,
+                &deduplicated_commitments,
+                &row_indices,
+                &cell_indices,
+                &cells,
+                &proofs_bytes,
+            )?;
+
+            // If there are no inputs, we return early with no error
+            //
+            if cells.is_empty() {
+                return Ok(());
+            }
+
+            // Deserialization
+            //
+            let row_commitment_: Vec<_> = deduplicated_commitments
+                .into_iter()
+                .map(|commitment| commitment.point)
+                .collect();
+            let proofs_ = deserialize_compressed_g1_points(proofs_bytes)?;
+            let coset_evals = deserialize_cells(cells)?;
+
+            // Computation
+            //
+            let ok = self
+                .verifier_ctx
+                .kzg_multipoint_verifier
+                .verify_multi_opening(
+                    &row_commitment_,
+                    &row_indices,
+                    &cell_indices,
+                    &coset_evals,
+                    &proofs_,
+                );
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
+    /// Verifies a batch of cells in the same way as `verify_cell_kzg_proof_batch`, but the cells
+    /// are combined using independent 128 bit random scalars sampled from `rng`, instead of the
+    /// powers of a Fiat-Shamir challenge.
+    ///
+    /// This avoids hashing every cell, which is a noticeable cost for large batches. Since the
+    /// result does not depend on the randomness for valid inputs, this will agree with
+    /// `verify_cell_kzg_proof_batch`, except with negligible probability.
+    ///
+    /// Note: `verify_cell_kzg_proof_batch` is the method that matches the spec and should
+    /// be preferred when determinism is needed.
+    pub fn verify_cell_kzg_proof_batch_with_rng<R: RngCore + CryptoRng + Send>(
+        &self,
+        rng: &mut R,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+            // Validation
+            //
+            validation::verify_cell_kzg_proof_batch(
+                &self.verifier_ctx.config,
+                &deduplicated_commitments,
+                &row_indices,
+                &cell_indices,
+                &cells,
+                &proofs_bytes,
+            )?;
+
+            // If there are no inputs, we return early with no error
+            //
+            if cells.is_empty() {
+                return Ok(());
+            }
+
+            // Deserialization
+            //
+            let row_commitment_ = deserialize_compressed_g1_points(deduplicated_commitments)?;
+            let proofs_ = deserialize_compressed_g1_points(proofs_bytes)?;
+            let coset_evals = deserialize_cells(cells)?;
+
+            // Computation
+            //
+            let ok = self
+                .verifier_ctx
+                .kzg_multipoint_verifier
+                .verify_multi_opening_with_rng(
+                    rng,
+                    &row_commitment_,
+                    &row_indices,
+                    &cell_indices,
+                    &coset_evals,
+                    &proofs_,
+                );
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
+    /// Verifies a batch of cells in the same way as `verify_cell_kzg_proof_batch`, but instead
+    /// of failing when the batch is invalid, this returns the position of every (cell, proof)
+    /// pair that is invalid.
+    ///
+    /// An empty list means that every cell in the batch is valid. When every cell is valid,
+    /// this is as fast as `verify_cell_kzg_proof_batch`.
+    ///
+    /// A (cell, proof) pair whose cell, proof or commitment cannot be deserialized is also
+    /// reported as invalid, and the remaining pairs are still verified.
+    ///
+    /// Returns an error if the inputs do not have the same length, or if a cell index or
+    /// commitment index is out of range.
+    pub fn verify_cell_kzg_proof_batch_with_culprits(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<Vec<usize>, Error> {
+        self.thread_pool.install(|| {
+            let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+            // Validation
+            //
+            validation::verify_cell_kzg_proof_batch_with_culprits(
+                &self.verifier_ctx.config,
+                &deduplicated_commitments,
+                &row_indices,
+                &cell_indices,
+                &cells,
+                &proofs_bytes,
+            )?;
+
+            // Deserialization
+            //
+            // A pair that cannot be deserialized is invalid, so it is reported without being
+            // verified. The remaining pairs are collected in the same order.
+            let row_commitments: Vec<_> = deduplicated_commitments
+                .into_iter()
+                .map(|commitment| deserialize_compressed_g1(commitment).ok())
+                .collect();
+
+            let mut malformed_positions = Vec::new();
+            let mut positions = Vec::with_capacity(cells.len());
+            let mut coset_evals = Vec::with_capacity(cells.len());
+            let mut proofs_ = Vec::with_capacity(cells.len());
+            for (position, (cell, proof)) in cells.into_iter().zip(proofs_bytes).enumerate() {
+                let commitment = row_commitments[row_indices[position] as usize];
+                let coset_eval = if cell.len() == self.verifier_ctx.config.bytes_per_cell() {
+                    deserialize_cell_to_scalars(cell).ok()
+                } else {
+                    None
+                };
+                let proof = deserialize_compressed_g1(proof).ok();
+
+                match (commitment, coset_eval, proof) {
+                    (Some(_), Some(coset_eval), Some(proof)) => {
+                        positions.push(position);
+                        coset_evals.push(coset_eval);
+                        proofs_.push(proof);
+                    }
+                    _ => malformed_positions.push(position),
+                }
+            }
+
+            // Only keep the commitments that could be deserialized, updating the
+            // commitment indices to point into the remaining commitments.
+            let mut row_commitment_ = Vec::with_capacity(row_commitments.len());
+            let mut updated_row_indices = Vec::with_capacity(row_commitments.len());
+            for commitment in row_commitments {
+                updated_row_indices.push(row_commitment_.len() as RowIndex);
+                row_commitment_.extend(commitment);
+            }
+            let row_indices: Vec<_> = positions
+                .iter()
+                .map(|&position| updated_row_indices[row_indices[position] as usize])
+                .collect();
+            let cell_indices: Vec<_> = positions
+                .iter()
+                .map(|&position| cell_indices[position])
+                .collect();
+
+            // Computation
+            //
+            let verifier = &self.verifier_ctx.kzg_multipoint_verifier;
+            let is_valid = |range: Range<usize>| {
+                verifier.verify_multi_opening(
+                    &row_commitment_,
+                    &row_indices[range.clone()],
+                    &cell_indices[range.clone()],
+                    &coset_evals[range.clone()],
+                    &proofs_[range],
+                )
+            };
+
+            let mut culprits = malformed_positions;
+            culprits.extend(
+                find_invalid_items(0..positions.len(), &is_valid)
+                    .into_iter()
+                    .map(|index| positions[index]),
+            );
+            culprits.sort_unstable();
+
+            Ok(culprits)
+        })
+    }
+
+    /// Verifies a column of the extended blob matrix, where the k'th cell and proof
+    /// are for the blob with the k'th commitment.
+    ///
+    /// This is equivalent to calling `verify_cell_kzg_proof_batch` with `column_index` as the
+    /// index of every cell, but it is faster since every cell is on the same coset.
+    pub fn verify_data_column(
+        &self,
+        column_index: CellIndex,
+        commitments: Vec<Bytes48Ref>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.thread_pool.install(|| {
+            // Validation
+            //
+            validation::verify_data_column(
+                &self.verifier_ctx.config,
+                column_index,
+                &commitments,
+                &cells,
+                &proofs_bytes,
+            )?;
+
+            // If there are no inputs, we return early with no error
+            //
+            if cells.is_empty() {
+                return Ok(());
+            }
+
+            // Deserialization
+            //
+            let commitments_ = deserialize_compressed_g1_points(commitments)?;
+            let proofs_ = deserialize_compressed_g1_points(proofs_bytes)?;
+            let coset_evals = deserialize_cells(cells)?;
+
+            // Computation
+            //
+            let ok = self
+                .verifier_ctx
+                .kzg_multipoint_verifier
+                .verify_multi_opening_same_coset(
+                    &commitments_,
+                    column_index,
+                    &coset_evals,
+                    &proofs_,
+                );
+
+            // Convert the boolean value into a Result
+            if ok {
+                Ok(())
+            } else {
+                Err(VerifierError::InvalidProof.into())
+            }
+        })
+    }
+
     pub(crate) fn recover_polynomial_coeff(
         &self,
         cell_indices: Vec<CellIndex>,
@@ -157,18 +700,88 @@ impl DASContext {
     ) -> Result<Vec<Scalar>, Error> {
         // Validation
         //
-        validation::recover_polynomial_coeff(&cell_indices, &cells)?;
+        validation::recover_polynomial_coeff(&self.verifier_ctx.config, &cell_indices, &cells)?;
+
+        // Deserialization
+        //
+        let (missing_cell_indices, flattened_coset_evaluations_normal_order) =
+            self.cells_to_domain_order(cell_indices, cells)?;
+
+        // Computation
+        //
+        // Recover the polynomial in monomial form, that one can use to generate the cells.
+        let recovered_polynomial_coeff = self
+            .verifier_ctx
+            .rs
+            .recover_polynomial_coefficient(
+                flattened_coset_evaluations_normal_order,
+                missing_cell_indices,
+            )
+            .map_err(VerifierError::from)?;
 
+        Ok(recovered_polynomial_coeff)
+    }
+
+    /// Recovers the polynomial for each row of a matrix, where every row contains the cells
+    /// at the same `cell_indices`.
+    ///
+    /// Since the same cells are missing from every row, the vanishing polynomial for the missing
+    /// cells is only computed once and shared between the rows.
+    ///
+    /// Note: The caller is expected to have validated the cell indices and the cells in each row.
+    pub(crate) fn recover_rows_polynomial_coeff(
+        &self,
+        cell_indices: Vec<CellIndex>,
+        rows: Vec<Vec<CellRef>>,
+    ) -> Result<Vec<Vec<Scalar>>, Error> {
         // Deserialization
         //
+        let rows = rows
+            .into_par_iter()
+            .map(|cells| self.cells_to_domain_order(cell_indices.clone(), cells))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Computation
+        //
+        let Some((missing_cell_indices, _)) = rows.first() else {
+            return Ok(Vec::new());
+        };
+        let rs = &self.verifier_ctx.rs;
+        let vanishing_poly = rs
+            .erasure_vanishing_poly(missing_cell_indices.clone())
+            .map_err(VerifierError::from)?;
+
+        let recovered_polynomial_coeffs = rows
+            .into_par_iter()
+            .map(|(_, flattened_coset_evaluations_normal_order)| {
+                rs.recover_polynomial_coefficient_with_vanishing_poly(
+                    flattened_coset_evaluations_normal_order,
+                    &vanishing_poly,
+                )
+                .map_err(VerifierError::from)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(recovered_polynomial_coeffs)
+    }
+
+    /// Deserializes the cells and places their evaluations in the order of the evaluation domain,
+    /// filling in the missing evaluations with zeroes.
+    ///
+    /// Returns the indices of the missing cells in domain order, along with the evaluations.
+    pub(crate) fn cells_to_domain_order(
+        &self,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+    ) -> Result<(BlockErasureIndices, Vec<Scalar>), Error> {
+        let config = &self.verifier_ctx.config;
+
         let coset_evaluations = deserialize_cells(cells)?;
         let cell_indices: Vec<usize> = cell_indices
             .into_iter()
             .map(|index| index as usize)
             .collect();
 
-        // Computation
-        //
         // Permute the cells, so they are in the order that you would expect, if you were
         // to compute an fft on the monomial form of the polynomial.
         //
@@ -176,41 +789,103 @@ impl DASContext {
         // which the API tries to hide.
         let (cell_indices_normal_order, flattened_coset_evaluations_normal_order) =
             Prover::recover_evaluations_in_domain_order(
-                FIELD_ELEMENTS_PER_EXT_BLOB,
+                config.field_elements_per_ext_blob(),
                 cell_indices,
                 coset_evaluations,
             )
             .expect("could not recover evaluations in domain order"); // TODO: We could make this an error instead of panic
 
         // Find all of the missing cell indices. This is needed for recovery.
-        let missing_cell_indices = find_missing_cell_indices(&cell_indices_normal_order);
-
-        // Recover the polynomial in monomial form, that one can use to generate the cells.
-        let recovered_polynomial_coeff = self
-            .verifier_ctx
-            .rs
-            .recover_polynomial_coefficient(
-                flattened_coset_evaluations_normal_order,
-                BlockErasureIndices(missing_cell_indices),
-            )
-            .map_err(VerifierError::from)?;
+        let missing_cell_indices =
+            find_missing_cell_indices(&cell_indices_normal_order, config.cells_per_ext_blob());
 
-        Ok(recovered_polynomial_coeff)
+        Ok((
+            BlockErasureIndices(missing_cell_indices),
+            flattened_coset_evaluations_normal_order,
+        ))
     }
 }
 
-mod validation {
+pub(crate) mod validation {
     use std::collections::HashSet;
 
     use crate::{
-        constants::{BYTES_PER_CELL, CELLS_PER_EXT_BLOB, EXTENSION_FACTOR},
-        verifier::VerifierError,
-        Bytes48Ref, CellIndex, CellRef, RowIndex,
+        config::DASConfig, extended_matrix::COLUMN_EXTENSION_FACTOR, verifier::VerifierError,
+        BlobRef, Bytes48Ref, CellIndex, CellRef, RowIndex,
     };
 
+    /// Validation logic for `verify_blob_kzg_proof_batch`
+    pub fn verify_blob_kzg_proof_batch(
+        blobs: &[BlobRef],
+        commitments: &[Bytes48Ref],
+        proofs: &[Bytes48Ref],
+    ) -> Result<(), VerifierError> {
+        // All inputs must have the same length according to the specs.
+        let same_length = (blobs.len() == commitments.len()) & (blobs.len() == proofs.len());
+        if !same_length {
+            return Err(
+                VerifierError::BlobBatchVerificationInputsMustHaveSameLength {
+                    blobs_len: blobs.len(),
+                    commitments_len: commitments.len(),
+                    proofs_len: proofs.len(),
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     /// Validation logic for `verify_cell_kzg_proof_batch`
-    pub fn verify_cell_kzg_proof_batch(
-        deduplicated_commitments_bytes: &[Bytes48Ref],
+    pub fn verify_cell_kzg_proof_batch<T>(
+        config: &DASConfig,
+        deduplicated_commitments: &[T],
+        commitment_indices: &[RowIndex],
+        cell_indices: &[CellIndex],
+        cells: &[CellRef],
+        proofs_bytes: &[Bytes48Ref],
+    ) -> Result<(), VerifierError> {
+        check_batch_inputs(
+            config,
+            deduplicated_commitments,
+            commitment_indices,
+            cell_indices,
+            cells,
+            proofs_bytes,
+        )?;
+
+        // Check that each cell has the right amount of bytes
+        check_cell_sizes(config, cell_indices, cells)?;
+
+        Ok(())
+    }
+
+    /// Validation logic for `verify_cell_kzg_proof_batch_with_culprits`
+    ///
+    /// Note: The size of each cell is not checked, since a cell with the wrong size is
+    /// reported as invalid instead.
+    pub(crate) fn verify_cell_kzg_proof_batch_with_culprits<T>(
+        config: &DASConfig,
+        deduplicated_commitments: &[T],
+        commitment_indices: &[RowIndex],
+        cell_indices: &[CellIndex],
+        cells: &[CellRef],
+        proofs_bytes: &[Bytes48Ref],
+    ) -> Result<(), VerifierError> {
+        check_batch_inputs(
+            config,
+            deduplicated_commitments,
+            commitment_indices,
+            cell_indices,
+            cells,
+            proofs_bytes,
+        )
+    }
+
+    /// Check that the inputs of a batch have the same length, and that the commitment
+    /// indices and cell indices are in range
+    fn check_batch_inputs<T>(
+        config: &DASConfig,
+        deduplicated_commitments: &[T],
         commitment_indices: &[RowIndex],
         cell_indices: &[CellIndex],
         cells: &[CellRef],
@@ -231,20 +906,20 @@ mod validation {
 
         // Check that the commitment indices are within the correct range
         for commitment_index in commitment_indices {
-            if *commitment_index >= deduplicated_commitments_bytes.len() as u64 {
+            if *commitment_index >= deduplicated_commitments.len() as u64 {
                 return Err(VerifierError::InvalidCommitmentIndex {
                     commitment_index: *commitment_index,
-                    max_number_of_commitments: deduplicated_commitments_bytes.len() as u64,
+                    max_number_of_commitments: deduplicated_commitments.len() as u64,
                 });
             }
         }
 
         // Check that cell indices are in the correct range
         for cell_index in cell_indices {
-            if *cell_index >= CELLS_PER_EXT_BLOB as u64 {
+            if *cell_index >= config.cells_per_ext_blob() as u64 {
                 return Err(VerifierError::CellIndexOutOfRange {
                     cell_index: *cell_index,
-                    max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
+                    max_number_of_cells: config.cells_per_ext_blob() as u64,
                 });
             }
         }
@@ -252,8 +927,62 @@ mod validation {
         Ok(())
     }
 
+    /// Validation logic for `verify_data_column`
+    pub(crate) fn verify_data_column(
+        config: &DASConfig,
+        column_index: CellIndex,
+        commitments_bytes: &[Bytes48Ref],
+        cells: &[CellRef],
+        proofs_bytes: &[Bytes48Ref],
+    ) -> Result<(), VerifierError> {
+        // Each cell needs a commitment and a proof
+        let same_length = (commitments_bytes.len() == cells.len())
+            & (commitments_bytes.len() == proofs_bytes.len());
+        if !same_length {
+            return Err(VerifierError::DataColumnInputsMustHaveSameLength {
+                commitments_len: commitments_bytes.len(),
+                cells_len: cells.len(),
+                proofs_len: proofs_bytes.len(),
+            });
+        }
+
+        // Check that the column index is in the correct range
+        if column_index >= config.cells_per_ext_blob() as u64 {
+            return Err(VerifierError::CellIndexOutOfRange {
+                cell_index: column_index,
+                max_number_of_cells: config.cells_per_ext_blob() as u64,
+            });
+        }
+
+        // Check that each cell has the right amount of bytes
+        for cell in cells {
+            check_cell_sizes(config, &[column_index], &[cell])?;
+        }
+
+        Ok(())
+    }
+
+    /// Validation logic for `CellBatchVerifier::push`
+    pub(crate) fn cell_batch_verifier_push(
+        config: &DASConfig,
+        cell_index: CellIndex,
+        cell: CellRef,
+    ) -> Result<(), VerifierError> {
+        // Check that the cell index is in the correct range
+        if cell_index >= config.cells_per_ext_blob() as u64 {
+            return Err(VerifierError::CellIndexOutOfRange {
+                cell_index,
+                max_number_of_cells: config.cells_per_ext_blob() as u64,
+            });
+        }
+
+        // Check that the cell has the right amount of bytes
+        check_cell_sizes(config, &[cell_index], &[cell])
+    }
+
     /// Validation logic for `recover_polynomial_coeff`
     pub(crate) fn recover_polynomial_coeff(
+        config: &DASConfig,
         cell_indices: &[CellIndex],
         cells: &[CellRef],
     ) -> Result<(), VerifierError> {
@@ -265,24 +994,117 @@ mod validation {
             });
         }
 
-        // Check that the Cell indices are within the expected range
-        for cell_index in cell_indices.iter() {
-            if *cell_index >= (CELLS_PER_EXT_BLOB as u64) {
-                return Err(VerifierError::CellIndexOutOfRange {
-                    cell_index: *cell_index,
-                    max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
+        recovery_cell_indices(config, cell_indices)?;
+
+        // Check that each cell has the right amount of bytes
+        check_cell_sizes(config, cell_indices, cells)?;
+
+        Ok(())
+    }
+
+    /// Validation logic for `decode_cells_with_errors`
+    pub(crate) fn decode_cells_with_errors(
+        config: &DASConfig,
+        cells: &[CellRef],
+    ) -> Result<(), VerifierError> {
+        // Check that we have exactly one cell for each cell index
+        if cells.len() < config.cells_per_ext_blob() {
+            return Err(VerifierError::NotEnoughCellsToReconstruct {
+                num_cells_received: cells.len(),
+                min_cells_needed: config.cells_per_ext_blob(),
+            });
+        }
+        if cells.len() > config.cells_per_ext_blob() {
+            return Err(VerifierError::TooManyCellsReceived {
+                num_cells_received: cells.len(),
+                max_cells_needed: config.cells_per_ext_blob(),
+            });
+        }
+
+        // Check that each cell has the right amount of bytes
+        let cell_indices: Vec<CellIndex> = (0..cells.len() as CellIndex).collect();
+        check_cell_sizes(config, &cell_indices, cells)
+    }
+
+    /// Validation logic for `recover_extended_matrix`
+    pub(crate) fn recover_extended_matrix(
+        config: &DASConfig,
+        rows: &[Vec<Option<CellRef>>],
+    ) -> Result<(), VerifierError> {
+        // Check that the number of rows is `COLUMN_EXTENSION_FACTOR` times the number of blobs,
+        // which is a power of two
+        if rows.len() < COLUMN_EXTENSION_FACTOR || !rows.len().is_power_of_two() {
+            return Err(VerifierError::InvalidNumberOfRows {
+                num_rows: rows.len(),
+            });
+        }
+
+        // Check that every row has a position for each cell, and that each cell has the
+        // right amount of bytes
+        for (row_index, row) in rows.iter().enumerate() {
+            if row.len() != config.cells_per_ext_blob() {
+                return Err(VerifierError::RowHasInvalidLength {
+                    row_index: row_index as RowIndex,
+                    num_cells: row.len(),
+                    expected_num_cells: config.cells_per_ext_blob(),
                 });
             }
+            for (cell_index, cell) in row.iter().enumerate() {
+                if let Some(cell) = cell {
+                    check_cell_sizes(config, &[cell_index as CellIndex], &[cell])?;
+                }
+            }
         }
 
-        // Check that each cell has the right amount of bytes
-        for (i, cell) in cells.iter().enumerate() {
-            if cell.len() != BYTES_PER_CELL {
-                // TODO: This check should always be true
-                return Err(VerifierError::CellDoesNotContainEnoughBytes {
-                    cell_index: cell_indices[i],
-                    num_bytes: cell.len(),
-                    expected_num_bytes: BYTES_PER_CELL,
+        Ok(())
+    }
+
+    /// Validation logic for `recover_matrix`
+    pub(crate) fn recover_matrix(
+        config: &DASConfig,
+        column_indices: &[CellIndex],
+        columns: &[Vec<CellRef>],
+    ) -> Result<(), VerifierError> {
+        // Check that the number of column indices is equal to the number of columns
+        if column_indices.len() != columns.len() {
+            return Err(VerifierError::NumCellIndicesNotEqualToNumCells {
+                num_cell_indices: column_indices.len(),
+                num_cells: columns.len(),
+            });
+        }
+
+        recovery_cell_indices(config, column_indices)?;
+
+        // Check that every column has a cell for each row, and that each cell has the right
+        // amount of bytes
+        let num_rows = columns.first().map_or(0, Vec::len);
+        for (column_index, column) in column_indices.iter().zip(columns) {
+            if column.len() != num_rows {
+                return Err(VerifierError::ColumnsHaveDifferentLengths {
+                    column_index: *column_index,
+                    num_cells: column.len(),
+                    expected_num_cells: num_rows,
+                });
+            }
+            for cell in column {
+                check_cell_sizes(config, &[*column_index], &[cell])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the cell indices can be used to recover all of the cells of an extended blob.
+    fn recovery_cell_indices(
+        config: &DASConfig,
+        cell_indices: &[CellIndex],
+    ) -> Result<(), VerifierError> {
+        // Check that the Cell indices are within the expected range
+        for cell_index in cell_indices.iter() {
+            if *cell_index >= (config.cells_per_ext_blob() as u64) {
+                return Err(VerifierError::CellIndexOutOfRange {
+                    cell_index: *cell_index,
+                    max_number_of_cells: config.cells_per_ext_blob() as u64,
                 });
             }
         }
@@ -293,25 +1115,46 @@ mod validation {
         }
 
         // Check that we have enough cells to perform a reconstruction
-        if cell_indices.len() < CELLS_PER_EXT_BLOB / EXTENSION_FACTOR {
+        let min_cells_needed = config.cells_per_ext_blob() / config.extension_factor();
+        if cell_indices.len() < min_cells_needed {
             return Err(VerifierError::NotEnoughCellsToReconstruct {
                 num_cells_received: cell_indices.len(),
-                min_cells_needed: CELLS_PER_EXT_BLOB / EXTENSION_FACTOR,
+                min_cells_needed,
             });
         }
 
         // Check that we don't have too many cells
         // ie more than we initially generated from the blob
-        if cell_indices.len() > CELLS_PER_EXT_BLOB {
+        if cell_indices.len() > config.cells_per_ext_blob() {
             return Err(VerifierError::TooManyCellsReceived {
                 num_cells_received: cell_indices.len(),
-                max_cells_needed: CELLS_PER_EXT_BLOB,
+                max_cells_needed: config.cells_per_ext_blob(),
             });
         }
 
         Ok(())
     }
 
+    /// Check that each cell has the number of bytes specified by the config
+    ///
+    /// Note: This assumes that there are as many cell indices as cells.
+    fn check_cell_sizes(
+        config: &DASConfig,
+        cell_indices: &[CellIndex],
+        cells: &[CellRef],
+    ) -> Result<(), VerifierError> {
+        for (cell_index, cell) in cell_indices.iter().zip(cells) {
+            if cell.len() != config.bytes_per_cell() {
+                return Err(VerifierError::CellDoesNotContainEnoughBytes {
+                    cell_index: *cell_index,
+                    num_bytes: cell.len(),
+                    expected_num_bytes: config.bytes_per_cell(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Check if all of the cell indices are unique
     fn are_cell_indices_unique(cell_indices: &[CellIndex]) -> bool {
         let len_cell_indices_non_dedup = cell_indices.len();
@@ -351,3 +1194,237 @@ mod validation {
         assert_eq!(expected_indices, indices);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::find_invalid_items;
+    use crate::{
+        constants::CELLS_PER_EXT_BLOB,
+        errors::VerifierError,
+        test_utils::{dummy_blob, dummy_blobs},
+        BlobRef, CellRef, DASContext, Error, ValidatedCommitment,
+    };
+
+    #[test]
+    fn test_find_invalid_items() {
+        let cases: [&[usize]; 5] = [&[], &[0], &[12], &[3, 4, 9], &[0, 1, 2, 3, 11, 12]];
+        for invalid in cases {
+            let is_valid =
+                |range: std::ops::Range<usize>| !invalid.iter().any(|item| range.contains(item));
+
+            assert_eq!(find_invalid_items(0..13, &is_valid), invalid);
+        }
+
+        let is_valid = |_| false;
+        assert!(find_invalid_items(0..0, &is_valid).is_empty());
+    }
+
+    #[test]
+    fn test_verify_cell_kzg_proof_batch_with_culprits() {
+        let ctx = DASContext::default();
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+        let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+        let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        // The cells that contain the blob data are all the same for this blob, so we
+        // also take cells from the extension.
+        let cell_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as u64).step_by(6).collect();
+        let commitments = vec![&commitment; cell_indices.len()];
+        let mut cell_refs: Vec<_> = cell_indices
+            .iter()
+            .map(|&index| cells[index as usize].as_ref())
+            .collect();
+        let proof_refs: Vec<_> = cell_indices
+            .iter()
+            .map(|&index| &proofs[index as usize])
+            .collect();
+
+        let culprits = ctx
+            .verify_cell_kzg_proof_batch_with_culprits(
+                commitments.clone(),
+                cell_indices.clone(),
+                cell_refs.clone(),
+                proof_refs.clone(),
+            )
+            .unwrap();
+        assert!(culprits.is_empty());
+
+        // Swap two cells, so that neither of them matches its proof
+        cell_refs.swap(4, 17);
+        let culprits = ctx
+            .verify_cell_kzg_proof_batch_with_culprits(
+                commitments.clone(),
+                cell_indices.clone(),
+                cell_refs.clone(),
+                proof_refs.clone(),
+            )
+            .unwrap();
+        assert_eq!(culprits, vec![4, 17]);
+        assert!(ctx
+            .verify_cell_kzg_proof_batch(
+                commitments.clone(),
+                cell_indices.clone(),
+                cell_refs.clone(),
+                proof_refs.clone()
+            )
+            .unwrap_err()
+            .invalid_proof());
+
+        // Pairs that cannot be deserialized are reported along with the invalid pairs
+        let non_canonical_cell = vec![0xff; cells[0].len()];
+        let short_cell = &cells[0][1..];
+        let malformed_point = [0xff; 48];
+        let mut commitments = commitments;
+        cell_refs[2] = &non_canonical_cell;
+        cell_refs[9] = short_cell;
+        let mut proof_refs = proof_refs;
+        proof_refs[11] = &malformed_point;
+        commitments[20] = &malformed_point;
+        let culprits = ctx
+            .verify_cell_kzg_proof_batch_with_culprits(
+                commitments,
+                cell_indices,
+                cell_refs,
+                proof_refs,
+            )
+            .unwrap();
+        assert_eq!(culprits, vec![2, 4, 9, 11, 17, 20]);
+    }
+
+    #[test]
+    fn test_verify_data_column() {
+        let ctx = DASContext::default();
+
+        let blobs = dummy_blobs(3);
+        let blob_refs: Vec<BlobRef> = blobs
+            .iter()
+            .map(|blob| blob.as_slice().try_into().unwrap())
+            .collect();
+        let data_columns = ctx.compute_data_columns(&blob_refs).unwrap();
+        let commitments: Vec<_> = data_columns.commitments.iter().collect();
+
+        for column in data_columns.columns.iter().step_by(17) {
+            let cells: Vec<CellRef> = column.cells.iter().map(|cell| cell.as_ref()).collect();
+            let proofs: Vec<_> = column.proofs.iter().collect();
+            ctx.verify_data_column(column.index, commitments.clone(), cells, proofs)
+                .unwrap();
+        }
+
+        // A column checked against the wrong index is rejected
+        let column = &data_columns.columns[0];
+        let cells: Vec<CellRef> = column.cells.iter().map(|cell| cell.as_ref()).collect();
+        let proofs: Vec<_> = column.proofs.iter().collect();
+        assert!(ctx
+            .verify_data_column(1, commitments.clone(), cells.clone(), proofs.clone())
+            .unwrap_err()
+            .invalid_proof());
+
+        // The commitments must be in the same order as the cells
+        let mut swapped_commitments = commitments.clone();
+        swapped_commitments.swap(0, 1);
+        assert!(ctx
+            .verify_data_column(0, swapped_commitments, cells.clone(), proofs.clone())
+            .unwrap_err()
+            .invalid_proof());
+
+        // Each cell needs a commitment and a proof
+        assert!(matches!(
+            ctx.verify_data_column(0, commitments[1..].to_vec(), cells, proofs),
+            Err(Error::Verifier(
+                VerifierError::DataColumnInputsMustHaveSameLength { .. }
+            ))
+        ));
+
+        // An empty column is valid
+        assert!(ctx
+            .verify_data_column(0, Vec::new(), Vec::new(), Vec::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_verify_cell_kzg_proof_batch_with_validated_commitments() {
+        let ctx = DASContext::default();
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+        let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+        let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        let validated_commitment = ValidatedCommitment::new(&commitment).unwrap();
+        assert_eq!(validated_commitment.to_bytes(), commitment);
+
+        let cell_indices = vec![3, 70, 127];
+        let commitments = vec![&validated_commitment; cell_indices.len()];
+        let cell_refs: Vec<_> = cell_indices
+            .iter()
+            .map(|&index| cells[index as usize].as_ref())
+            .collect();
+        let proof_refs: Vec<_> = cell_indices
+            .iter()
+            .map(|&index| &proofs[index as usize])
+            .collect();
+
+        ctx.verify_cell_kzg_proof_batch_with_validated_commitments(
+            commitments.clone(),
+            cell_indices.clone(),
+            cell_refs.clone(),
+            proof_refs.clone(),
+        )
+        .unwrap();
+
+        // Use the proofs for the wrong cells
+        let mut wrong_proof_refs = proof_refs;
+        wrong_proof_refs.rotate_left(1);
+        assert!(ctx
+            .verify_cell_kzg_proof_batch_with_validated_commitments(
+                commitments,
+                cell_indices,
+                cell_refs,
+                wrong_proof_refs,
+            )
+            .unwrap_err()
+            .invalid_proof());
+
+        // Commitments that are not valid points are rejected when they are validated
+        assert!(ValidatedCommitment::new(&[0xff; 48]).is_err());
+    }
+
+    #[test]
+    fn test_verify_cell_kzg_proof_batch_with_rng() {
+        let ctx = DASContext::default();
+        let blob = dummy_blob(0);
+        let blob = blob.as_slice().try_into().unwrap();
+        let commitment = ctx.blob_to_kzg_commitment(blob).unwrap();
+        let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        let commitments = vec![&commitment; CELLS_PER_EXT_BLOB];
+        let cell_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as u64).collect();
+        let mut cell_refs: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
+        let proof_refs: Vec<_> = proofs.iter().collect();
+
+        let mut rng = StdRng::from_entropy();
+        ctx.verify_cell_kzg_proof_batch_with_rng(
+            &mut rng,
+            commitments.clone(),
+            cell_indices.clone(),
+            cell_refs.clone(),
+            proof_refs.clone(),
+        )
+        .unwrap();
+
+        // Swap a cell that contains blob data with a cell from the extension
+        cell_refs.swap(0, CELLS_PER_EXT_BLOB - 1);
+        assert!(ctx
+            .verify_cell_kzg_proof_batch_with_rng(
+                &mut rng,
+                commitments,
+                cell_indices,
+                cell_refs,
+                proof_refs,
+            )
+            .unwrap_err()
+            .invalid_proof());
+    }
+}
@@ -0,0 +1,94 @@
+use common::collect_test_files;
+use rust_eth_kzg::constants::BYTES_PER_BLOB;
+use serde_::TestVector;
+use std::fs;
+
+mod common;
+
+mod serde_ {
+    use crate::common::{bytes_from_hex, UnsafeBytes};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct YamlInput {
+        blob: String,
+        commitment: String,
+    }
+
+    type YamlOutput = String;
+
+    #[derive(Deserialize)]
+    struct YamlTestVector {
+        input: YamlInput,
+        output: Option<YamlOutput>,
+    }
+
+    pub struct TestVector {
+        pub blob: UnsafeBytes,
+        pub commitment: UnsafeBytes,
+        pub proof: Option<UnsafeBytes>,
+    }
+
+    impl TestVector {
+        pub fn from_str(yaml_data: &str) -> Self {
+            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
+            TestVector::from(yaml_test_vector)
+        }
+    }
+
+    impl From<YamlTestVector> for TestVector {
+        fn from(yaml_test_vector: YamlTestVector) -> Self {
+            let input = yaml_test_vector.input;
+
+            TestVector {
+                blob: bytes_from_hex(&input.blob),
+                commitment: bytes_from_hex(&input.commitment),
+                proof: yaml_test_vector.output.map(|proof| bytes_from_hex(&proof)),
+            }
+        }
+    }
+}
+
+const TEST_DIR: &str = "../test_vectors/compute_blob_kzg_proof";
+#[test]
+fn test_compute_blob_kzg_proof() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = TestVector::from_str(&yaml_data);
+
+        let blob: &[u8; BYTES_PER_BLOB] = match (&test.blob[..]).try_into() {
+            Ok(blob) => blob,
+            Err(_) => {
+                // Blob does not have a valid size
+                assert!(test.proof.is_none());
+                continue;
+            }
+        };
+
+        let commitment = match (&test.commitment[..]).try_into() {
+            Ok(commitment) => commitment,
+            Err(_) => {
+                // Commitment does not have a valid size
+                assert!(test.proof.is_none());
+                continue;
+            }
+        };
+
+        match ctx.compute_blob_kzg_proof(blob, commitment) {
+            Ok(proof) => {
+                let expected_proof = test.proof.unwrap();
+
+                assert_eq!(&proof[..], &expected_proof);
+            }
+            Err(_) => {
+                // On an error, we expect the output to be null
+                assert!(test.proof.is_none());
+            }
+        };
+    }
+}
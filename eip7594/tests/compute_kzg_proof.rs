@@ -0,0 +1,109 @@
+use common::collect_test_files;
+use rust_eth_kzg::constants::BYTES_PER_BLOB;
+use serde_::TestVector;
+use std::fs;
+
+mod common;
+
+mod serde_ {
+    use crate::common::{bytes_from_hex, UnsafeBytes};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct YamlInput {
+        blob: String,
+        z: String,
+    }
+
+    type YamlOutput = (String, String);
+
+    #[derive(Deserialize)]
+    struct YamlTestVector {
+        input: YamlInput,
+        output: Option<YamlOutput>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct KZGProofAndEvaluation {
+        pub proof: UnsafeBytes,
+        pub y: UnsafeBytes,
+    }
+
+    pub struct TestVector {
+        pub blob: UnsafeBytes,
+        pub z: UnsafeBytes,
+        pub proof_and_y: Option<KZGProofAndEvaluation>,
+    }
+
+    impl TestVector {
+        pub fn from_str(yaml_data: &str) -> Self {
+            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
+            TestVector::from(yaml_test_vector)
+        }
+    }
+
+    impl From<YamlTestVector> for TestVector {
+        fn from(yaml_test_vector: YamlTestVector) -> Self {
+            let blob = bytes_from_hex(&yaml_test_vector.input.blob);
+            let z = bytes_from_hex(&yaml_test_vector.input.z);
+
+            let proof_and_y = yaml_test_vector
+                .output
+                .map(|(proof, y)| KZGProofAndEvaluation {
+                    proof: bytes_from_hex(&proof),
+                    y: bytes_from_hex(&y),
+                });
+
+            TestVector {
+                blob,
+                z,
+                proof_and_y,
+            }
+        }
+    }
+}
+
+const TEST_DIR: &str = "../test_vectors/compute_kzg_proof";
+#[test]
+fn test_compute_kzg_proof() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = TestVector::from_str(&yaml_data);
+
+        let blob: &[u8; BYTES_PER_BLOB] = match (&test.blob[..]).try_into() {
+            Ok(blob) => blob,
+            Err(_) => {
+                // Blob does not have a valid size
+                assert!(test.proof_and_y.is_none());
+                continue;
+            }
+        };
+
+        let z: &[u8; 32] = match (&test.z[..]).try_into() {
+            Ok(z) => z,
+            Err(_) => {
+                // z does not have a valid size
+                assert!(test.proof_and_y.is_none());
+                continue;
+            }
+        };
+
+        match ctx.compute_kzg_proof(blob, z) {
+            Ok((proof, y)) => {
+                let expected = test.proof_and_y.unwrap();
+
+                assert_eq!(&proof[..], &expected.proof);
+                assert_eq!(&y[..], &expected.y);
+            }
+            Err(_) => {
+                // On an error, we expect the output to be null
+                assert!(test.proof_and_y.is_none());
+            }
+        };
+    }
+}
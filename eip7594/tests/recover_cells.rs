@@ -0,0 +1,93 @@
+use common::collect_test_files;
+use serde_::TestVector;
+use std::fs;
+
+mod common;
+
+mod serde_ {
+    use crate::common::{bytes_from_hex, UnsafeBytes};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct YamlInput {
+        // Note: These test vectors use `cell_ids` instead of `cell_indices`
+        cell_ids: Vec<u64>,
+        cells: Vec<String>,
+    }
+
+    type YamlOutput = Vec<String>;
+
+    #[derive(Deserialize)]
+    struct YamlTestVector {
+        input: YamlInput,
+        output: Option<YamlOutput>,
+    }
+
+    pub struct TestVector {
+        pub input_cell_indices: Vec<u64>,
+        pub input_cells: Vec<UnsafeBytes>,
+        pub cells: Option<Vec<UnsafeBytes>>,
+    }
+
+    impl TestVector {
+        pub fn from_str(yaml_data: &str) -> Self {
+            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
+            TestVector::from(yaml_test_vector)
+        }
+    }
+
+    impl From<YamlTestVector> for TestVector {
+        fn from(yaml_test_vector: YamlTestVector) -> Self {
+            let input_cell_indices = yaml_test_vector.input.cell_ids;
+
+            let input_cells: Vec<_> = yaml_test_vector
+                .input
+                .cells
+                .iter()
+                .map(|cell| bytes_from_hex(cell))
+                .collect();
+
+            let cells = yaml_test_vector
+                .output
+                .map(|cells| cells.iter().map(|cell| bytes_from_hex(cell)).collect());
+
+            TestVector {
+                input_cell_indices,
+                input_cells,
+                cells,
+            }
+        }
+    }
+}
+
+const TEST_DIR: &str = "../test_vectors/recover_all_cells";
+#[test]
+fn test_recover_cells() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(&test_file).unwrap();
+        let test = TestVector::from_str(&yaml_data);
+
+        // The number of bytes in each cell is checked by the library
+        let input_cells = test.input_cells.iter().map(Vec::as_slice).collect();
+
+        match ctx.recover_cells(test.input_cell_indices, input_cells) {
+            Ok(cells) => {
+                let expected_cells = test.cells.unwrap();
+
+                assert_eq!(cells.len(), expected_cells.len());
+                for (got_cell, expected_cell) in cells.iter().zip(expected_cells) {
+                    assert_eq!(&got_cell[..], expected_cell);
+                }
+            }
+            Err(_) => {
+                // On an error, we expect the output to be null
+                assert!(test.cells.is_none());
+            }
+        };
+    }
+}
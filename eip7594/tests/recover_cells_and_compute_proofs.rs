@@ -87,20 +87,8 @@ fn test_recover_cells_and_proofs() {
         let yaml_data = fs::read_to_string(&test_file).unwrap();
         let test = TestVector::from_str(&yaml_data);
 
-        let input_cells: Result<_, _> = test
-            .input_cells
-            .iter()
-            .map(Vec::as_slice)
-            .map(|v| v.try_into())
-            .collect();
-
-        let input_cells = match input_cells {
-            Ok(input_cells) => input_cells,
-            Err(_) => {
-                assert!(test.proofs_and_cells.is_none());
-                continue;
-            }
-        };
+        // The number of bytes in each cell is checked by the library
+        let input_cells = test.input_cells.iter().map(Vec::as_slice).collect();
 
         match ctx.recover_cells_and_proofs(test.input_cell_indices, input_cells) {
             Ok((cells, proofs)) => {
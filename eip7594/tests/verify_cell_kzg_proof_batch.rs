@@ -86,20 +86,8 @@ fn test_verify_cell_kzg_proof_batch() {
         let yaml_data = fs::read_to_string(&test_file).unwrap();
         let test = TestVector::from_str(&yaml_data);
 
-        let cells: Result<_, _> = test
-            .cells
-            .iter()
-            .map(Vec::as_slice)
-            .map(|v| v.try_into())
-            .collect();
-
-        let cells = match cells {
-            Ok(cells) => cells,
-            Err(_) => {
-                assert!(test.output.is_none());
-                continue;
-            }
-        };
+        // The number of bytes in each cell is checked by the library
+        let cells = test.cells.iter().map(Vec::as_slice).collect();
 
         let commitments: Result<_, _> = test
             .commitments
@@ -0,0 +1,111 @@
+use common::collect_test_files;
+use serde_::TestVector;
+use std::fs;
+
+mod common;
+
+mod serde_ {
+    use crate::common::{bytes_from_hex, UnsafeBytes};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct YamlInput {
+        commitment: String,
+        z: String,
+        y: String,
+        proof: String,
+    }
+
+    type YamlOutput = bool;
+
+    #[derive(Deserialize)]
+    struct YamlTestVector {
+        input: YamlInput,
+        output: Option<YamlOutput>,
+    }
+
+    pub struct TestVector {
+        pub commitment: UnsafeBytes,
+        pub z: UnsafeBytes,
+        pub y: UnsafeBytes,
+        pub proof: UnsafeBytes,
+        pub output: Option<bool>,
+    }
+
+    impl TestVector {
+        pub fn from_str(yaml_data: &str) -> Self {
+            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
+            TestVector::from(yaml_test_vector)
+        }
+    }
+
+    impl From<YamlTestVector> for TestVector {
+        fn from(yaml_test_vector: YamlTestVector) -> Self {
+            let input = yaml_test_vector.input;
+
+            TestVector {
+                commitment: bytes_from_hex(&input.commitment),
+                z: bytes_from_hex(&input.z),
+                y: bytes_from_hex(&input.y),
+                proof: bytes_from_hex(&input.proof),
+                output: yaml_test_vector.output,
+            }
+        }
+    }
+}
+
+const TEST_DIR: &str = "../test_vectors/verify_kzg_proof";
+#[test]
+fn test_verify_kzg_proof() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(&test_file).unwrap();
+        let test = TestVector::from_str(&yaml_data);
+
+        let commitment = match (&test.commitment[..]).try_into() {
+            Ok(commitment) => commitment,
+            Err(_) => {
+                assert!(test.output.is_none());
+                continue;
+            }
+        };
+        let z = match (&test.z[..]).try_into() {
+            Ok(z) => z,
+            Err(_) => {
+                assert!(test.output.is_none());
+                continue;
+            }
+        };
+        let y = match (&test.y[..]).try_into() {
+            Ok(y) => y,
+            Err(_) => {
+                assert!(test.output.is_none());
+                continue;
+            }
+        };
+        let proof = match (&test.proof[..]).try_into() {
+            Ok(proof) => proof,
+            Err(_) => {
+                assert!(test.output.is_none());
+                continue;
+            }
+        };
+
+        match ctx.verify_kzg_proof(commitment, z, y, proof) {
+            Ok(_) => {
+                // We arrive at this point if the proof verified as true
+                assert!(test.output.unwrap())
+            }
+            Err(x) if x.invalid_proof() => {
+                assert!(!test.output.unwrap());
+            }
+            Err(_) => {
+                assert!(test.output.is_none());
+            }
+        };
+    }
+}